@@ -0,0 +1,317 @@
+//! Blocking counterpart of [`crate::client::CraftgateClient`] for applications without an async
+//! runtime.
+//!
+//! Every method runs the matching method of the async client to completion on a current-thread
+//! runtime owned by the blocking client, so both clients send the same requests through the same
+//! middleware. Like `reqwest::blocking`, the blocking client must not be used from within an async
+//! runtime.
+
+use std::sync::Arc;
+
+#[cfg(feature = "reporting")]
+use bytes::Bytes;
+#[cfg(feature = "reporting")]
+use time::Date;
+#[cfg(feature = "payment")]
+use time::OffsetDateTime;
+use tokio::runtime::{self, Runtime};
+use url::Url;
+
+#[cfg(any(
+    feature = "onboarding",
+    feature = "payment",
+    feature = "settlement",
+    feature = "bank-account-tracking"
+))]
+use crate::client::CraftgateError;
+use crate::client::{self, ClientOptions};
+#[cfg(feature = "bank-account-tracking")]
+use crate::id::BankAccountTrackingRecordId;
+#[cfg(any(feature = "onboarding", feature = "wallet"))]
+use crate::id::MemberId;
+#[cfg(feature = "settlement")]
+use crate::id::PayoutAccountId;
+#[cfg(feature = "payment")]
+use crate::id::{BkmExpressTicketId, CheckoutToken, PaymentId};
+#[cfg(feature = "payment")]
+use crate::idempotency::IdempotencyKey;
+#[cfg(feature = "bank-account-tracking")]
+use crate::request::bank_account_tracking::{
+    search_records::SearchBankAccountTrackingRecordsRequest, BankAccountTrackingRecord,
+};
+#[cfg(feature = "onboarding")]
+use crate::request::onboarding::{
+    create_member::CreateMemberRequest, search_members::SearchMembersRequest,
+    update_member::UpdateMemberRequest, Member,
+};
+#[cfg(feature = "payment")]
+use crate::request::payment::{
+    apple_pay::{ApplePayMerchantSession, ApplePayMerchantSessionRequest},
+    bkm_express::{CompleteBkmExpressRequest, InitBkmExpressRequest, InitBkmExpressResponse},
+    checkout_payment::{CheckoutPaymentInitiationRequest, CheckoutPaymentInitiationResponse},
+    create_payment::CreatePaymentRequest,
+    garanti_pay::{InitGarantiPayPaymentRequest, InitGarantiPayPaymentResponse},
+    masterpass::{
+        CheckMasterpassUserRequest, CheckMasterpassUserResponse, MasterpassPaymentCompleteRequest,
+        MasterpassPaymentThreeDSInitRequest, MasterpassPaymentThreeDSInitResponse,
+        MasterpassPaymentTokenGenerateRequest, MasterpassPaymentTokenGenerateResponse,
+        MasterpassRetrieveLoyaltiesRequest,
+    },
+    multi_payment::MultiPayment,
+    retrieve_loyalties::{RetrieveLoyaltiesRequest, RetrieveLoyaltiesResponse},
+    search_payments::SearchPaymentsRequest,
+    three_ds_payment::{InitThreeDSPaymentRequest, InitThreeDSPaymentResponse},
+    transaction_refund::{PaymentTransactionRefund, SearchPaymentTransactionRefundsRequest},
+    Payment,
+};
+#[cfg(feature = "reporting")]
+use crate::request::reporting::ReportFileType;
+#[cfg(feature = "settlement")]
+use crate::request::settlement::{
+    instant_wallet_settlement::{CreateInstantWalletSettlementRequest, SettlementResponse},
+    payout_account::{
+        CreatePayoutAccountRequest, CreatePayoutAccountResponse, PayoutAccount,
+        SearchPayoutAccountsRequest, UpdatePayoutAccountRequest,
+    },
+    payout_completed_transaction::{
+        PayoutCompletedTransaction, SearchPayoutCompletedTransactionsRequest,
+    },
+};
+#[cfg(feature = "wallet")]
+use crate::request::wallet::{
+    deposit_payment::{
+        CreateDepositPaymentRequest, DepositPayment, InitCheckoutDepositPaymentRequest,
+        InitThreeDSDepositPaymentRequest,
+    },
+    fund_transfer_deposit::{CreateFundTransferDepositPaymentRequest, FundTransferDepositPayment},
+    Wallet,
+};
+#[cfg(any(
+    feature = "onboarding",
+    feature = "payment",
+    feature = "settlement",
+    feature = "bank-account-tracking"
+))]
+use crate::response::PaginatedResponse;
+
+#[derive(Clone)]
+// Only read by the requests of the API area features.
+#[cfg_attr(
+    not(any(
+        feature = "onboarding",
+        feature = "payment",
+        feature = "settlement",
+        feature = "bank-account-tracking"
+    )),
+    allow(dead_code)
+)]
+pub struct CraftgateClient {
+    inner: client::CraftgateClient,
+    runtime: Arc<Runtime>,
+}
+
+impl CraftgateClient {
+    pub fn new(sandbox: bool, api_key: &str, secret_key: &str) -> Self {
+        Self::new_with_options(
+            api_key,
+            secret_key,
+            ClientOptions::builder().sandbox(sandbox).build(),
+        )
+    }
+
+    /// Sends requests to `base_url` instead of the Craftgate API, e.g. a mock server in tests
+    pub fn new_with_base_url(base_url: Url, api_key: &str, secret_key: &str) -> Self {
+        Self::new_with_options(
+            api_key,
+            secret_key,
+            ClientOptions::builder().base_url(Some(base_url)).build(),
+        )
+    }
+
+    pub fn new_with_options(api_key: &str, secret_key: &str, options: ClientOptions) -> Self {
+        let runtime = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime of the blocking client can be built");
+
+        Self {
+            inner: client::CraftgateClient::new_with_options(api_key, secret_key, options),
+            runtime: Arc::new(runtime),
+        }
+    }
+}
+
+/// Defines a blocking method for each listed method of the async client, with the same arguments
+macro_rules! blocking_methods {
+    ($($(#[$meta:meta])* fn $name:ident(&self $(, $arg:ident: $ty:ty)*) -> $ret:ty;)*) => {
+        impl CraftgateClient {
+            $(
+                #[doc = concat!(
+                    "Blocking version of [`CraftgateClient::",
+                    stringify!($name),
+                    "`](crate::client::CraftgateClient::",
+                    stringify!($name),
+                    ")"
+                )]
+                $(#[$meta])*
+                pub fn $name(&self $(, $arg: $ty)*) -> Result<$ret, CraftgateError> {
+                    self.runtime.block_on(self.inner.$name($($arg),*))
+                }
+            )*
+        }
+    };
+}
+
+blocking_methods! {
+    #[cfg(feature = "onboarding")]
+    fn create_member(&self, member: CreateMemberRequest) -> Member;
+    #[cfg(feature = "onboarding")]
+    fn update_member(&self, member_id: MemberId, member: UpdateMemberRequest) -> Member;
+    #[cfg(feature = "onboarding")]
+    fn retrieve_member(&self, member_id: MemberId) -> Option<Member>;
+    #[cfg(feature = "onboarding")]
+    fn search_members(&self, params: SearchMembersRequest) -> PaginatedResponse<Member>;
+
+    #[cfg(feature = "payment")]
+    fn initiate_checkout_payment(
+        &self,
+        params: CheckoutPaymentInitiationRequest
+    ) -> CheckoutPaymentInitiationResponse;
+    #[cfg(feature = "payment")]
+    fn initiate_checkout_payment_unchecked(
+        &self,
+        params: CheckoutPaymentInitiationRequest
+    ) -> CheckoutPaymentInitiationResponse;
+    #[cfg(feature = "payment")]
+    fn checkout_payment_inquiry(&self, token: &CheckoutToken) -> Payment;
+    #[cfg(feature = "payment")]
+    fn expire_common_page_token(&self, token: &CheckoutToken) -> ();
+    #[cfg(feature = "payment")]
+    fn retrieve_multi_payment(&self, token: &CheckoutToken) -> MultiPayment;
+    #[cfg(feature = "payment")]
+    fn retrieve_payment(&self, id: PaymentId) -> Payment;
+    #[cfg(feature = "payment")]
+    fn search_payments(&self, params: SearchPaymentsRequest) -> PaginatedResponse<Payment>;
+    #[cfg(feature = "payment")]
+    fn find_payment(
+        &self,
+        conversation_id: Option<&str>,
+        external_id: Option<&str>,
+        created_since: Option<OffsetDateTime>
+    ) -> Option<Payment>;
+    #[cfg(feature = "payment")]
+    fn search_payment_transaction_refunds(
+        &self,
+        params: SearchPaymentTransactionRefundsRequest
+    ) -> PaginatedResponse<PaymentTransactionRefund>;
+    #[cfg(feature = "payment")]
+    fn retrieve_loyalties(&self, params: RetrieveLoyaltiesRequest) -> RetrieveLoyaltiesResponse;
+    #[cfg(feature = "payment")]
+    fn create_payment(&self, params: CreatePaymentRequest, key: &IdempotencyKey) -> Payment;
+    #[cfg(feature = "payment")]
+    fn init_3ds_payment(&self, params: InitThreeDSPaymentRequest) -> InitThreeDSPaymentResponse;
+    #[cfg(feature = "payment")]
+    fn complete_3ds_payment(&self, payment_id: PaymentId, key: &IdempotencyKey) -> Payment;
+    #[cfg(feature = "payment")]
+    fn check_masterpass_user(
+        &self,
+        params: CheckMasterpassUserRequest
+    ) -> CheckMasterpassUserResponse;
+    #[cfg(feature = "payment")]
+    fn generate_masterpass_payment_token(
+        &self,
+        params: MasterpassPaymentTokenGenerateRequest
+    ) -> MasterpassPaymentTokenGenerateResponse;
+    #[cfg(feature = "payment")]
+    fn complete_masterpass_payment(&self, params: MasterpassPaymentCompleteRequest) -> Payment;
+    #[cfg(feature = "payment")]
+    fn init_3ds_masterpass_payment(
+        &self,
+        params: MasterpassPaymentThreeDSInitRequest
+    ) -> MasterpassPaymentThreeDSInitResponse;
+    #[cfg(feature = "payment")]
+    fn retrieve_masterpass_loyalty(
+        &self,
+        params: MasterpassRetrieveLoyaltiesRequest
+    ) -> RetrieveLoyaltiesResponse;
+    #[cfg(feature = "payment")]
+    fn init_garanti_pay_payment(
+        &self,
+        params: InitGarantiPayPaymentRequest
+    ) -> InitGarantiPayPaymentResponse;
+    #[cfg(feature = "payment")]
+    fn init_bkm_express_payment(&self, params: InitBkmExpressRequest) -> InitBkmExpressResponse;
+    #[cfg(feature = "payment")]
+    fn complete_bkm_express_payment(
+        &self,
+        params: CompleteBkmExpressRequest,
+        key: &IdempotencyKey
+    ) -> Payment;
+    #[cfg(feature = "payment")]
+    fn retrieve_bkm_express_payment(&self, ticket_id: &BkmExpressTicketId) -> Payment;
+    #[cfg(feature = "payment")]
+    fn retrieve_apple_pay_merchant_session(
+        &self,
+        params: ApplePayMerchantSessionRequest
+    ) -> ApplePayMerchantSession;
+
+    #[cfg(feature = "wallet")]
+    fn retrieve_member_wallet(&self, member_id: MemberId) -> Wallet;
+    #[cfg(feature = "wallet")]
+    fn create_deposit_payment(&self, params: CreateDepositPaymentRequest) -> DepositPayment;
+    #[cfg(feature = "wallet")]
+    fn init_3ds_deposit_payment(
+        &self,
+        params: InitThreeDSDepositPaymentRequest
+    ) -> InitThreeDSPaymentResponse;
+    #[cfg(feature = "wallet")]
+    fn complete_3ds_deposit_payment(&self, payment_id: PaymentId) -> DepositPayment;
+    #[cfg(feature = "wallet")]
+    fn init_checkout_deposit_payment(
+        &self,
+        params: InitCheckoutDepositPaymentRequest
+    ) -> CheckoutPaymentInitiationResponse;
+    #[cfg(feature = "wallet")]
+    fn create_fund_transfer_deposit_payment(
+        &self,
+        params: CreateFundTransferDepositPaymentRequest
+    ) -> FundTransferDepositPayment;
+
+    #[cfg(feature = "settlement")]
+    fn create_instant_wallet_settlement(
+        &self,
+        params: CreateInstantWalletSettlementRequest
+    ) -> SettlementResponse;
+    #[cfg(feature = "settlement")]
+    fn create_payout_account(&self, params: CreatePayoutAccountRequest) -> CreatePayoutAccountResponse;
+    #[cfg(feature = "settlement")]
+    fn update_payout_account(&self, id: PayoutAccountId, params: UpdatePayoutAccountRequest) -> ();
+    #[cfg(feature = "settlement")]
+    fn delete_payout_account(&self, id: PayoutAccountId) -> ();
+    #[cfg(feature = "settlement")]
+    fn search_payout_accounts(
+        &self,
+        params: SearchPayoutAccountsRequest
+    ) -> PaginatedResponse<PayoutAccount>;
+    #[cfg(feature = "settlement")]
+    fn search_payout_completed_transactions(
+        &self,
+        params: SearchPayoutCompletedTransactionsRequest
+    ) -> PaginatedResponse<PayoutCompletedTransaction>;
+
+    #[cfg(feature = "bank-account-tracking")]
+    fn search_bank_account_tracking_records(
+        &self,
+        params: SearchBankAccountTrackingRecordsRequest
+    ) -> PaginatedResponse<BankAccountTrackingRecord>;
+    #[cfg(feature = "bank-account-tracking")]
+    fn retrieve_bank_account_tracking_record(
+        &self,
+        id: BankAccountTrackingRecordId
+    ) -> BankAccountTrackingRecord;
+
+    #[cfg(feature = "reporting")]
+    fn retrieve_daily_transaction_report(&self, date: Date, file_type: ReportFileType) -> Bytes;
+    #[cfg(feature = "reporting")]
+    fn retrieve_daily_payment_report(&self, date: Date, file_type: ReportFileType) -> Bytes;
+}
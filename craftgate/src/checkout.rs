@@ -0,0 +1,105 @@
+//! Lifecycle of a payment made on the Craftgate hosted checkout form.
+
+use std::future::Future;
+use std::time::Duration;
+
+use time::OffsetDateTime;
+
+use crate::api_error::ErrorCode;
+use crate::client::{CraftgateClient, CraftgateError};
+use crate::id::CheckoutToken;
+use crate::request::payment::checkout_payment::{
+    CheckoutPaymentInitiationRequest, CheckoutPaymentInitiationResponse,
+};
+use crate::request::payment::Payment;
+use crate::transport::Transport;
+
+/// Validation error Craftgate responds with to the inquiry of a checkout form the buyer has not
+/// paid yet
+pub const PAYMENT_NOT_COMPLETED: ErrorCode = ErrorCode(5005);
+
+/// A checkout form initiated with [`CraftgateClient::initiate_checkout_payment`], tracking its
+/// token, page URL and expiry.
+pub struct CheckoutSession<'a, T> {
+    client: &'a CraftgateClient<T>,
+    token: CheckoutToken,
+    page_url: String,
+    expire_date: OffsetDateTime,
+}
+
+impl<'a, T: Transport> CheckoutSession<'a, T> {
+    /// Initiates a checkout form for `params`
+    pub async fn start(
+        client: &'a CraftgateClient<T>,
+        params: CheckoutPaymentInitiationRequest,
+    ) -> Result<CheckoutSession<'a, T>, CraftgateError> {
+        let resp = client.initiate_checkout_payment(params).await?;
+
+        Ok(Self::new(client, resp))
+    }
+
+    /// Tracks a checkout form that is already initiated
+    pub fn new(client: &'a CraftgateClient<T>, resp: CheckoutPaymentInitiationResponse) -> Self {
+        Self {
+            client,
+            token: resp.token,
+            page_url: resp.page_url,
+            expire_date: resp.token_expire_date,
+        }
+    }
+
+    pub fn token(&self) -> &CheckoutToken {
+        &self.token
+    }
+
+    /// URL of the checkout form the buyer should be redirected to
+    pub fn page_url(&self) -> &str {
+        &self.page_url
+    }
+
+    pub fn expire_date(&self) -> OffsetDateTime {
+        self.expire_date
+    }
+
+    pub fn is_expired(&self) -> bool {
+        OffsetDateTime::now_utc() >= self.expire_date
+    }
+
+    /// Inquires the payment every `interval` until it reaches a terminal
+    /// [`PaymentStatus`](crate::request::payment::PaymentStatus). Returns `None` if the token
+    /// expires first.
+    ///
+    /// Craftgate responds with [`PAYMENT_NOT_COMPLETED`] until the buyer completes the form, so
+    /// that error is retried and any other is returned. `sleep` waits between inquiries, e.g.
+    /// `tokio::time::sleep`.
+    pub async fn poll<S, Fut>(
+        &self,
+        interval: Duration,
+        mut sleep: S,
+    ) -> Result<Option<Payment>, CraftgateError>
+    where
+        S: FnMut(Duration) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        loop {
+            if self.is_expired() {
+                return Ok(None);
+            }
+
+            match self.client.checkout_payment_inquiry(&self.token).await {
+                Ok(payment) if payment.payment_status.is_terminal() => return Ok(Some(payment)),
+                Ok(_) => {}
+                Err(CraftgateError::ValidationError { code, .. })
+                    if code == PAYMENT_NOT_COMPLETED => {}
+                Err(err) => return Err(err),
+            }
+
+            sleep(interval).await;
+        }
+    }
+
+    /// Expires the token so the form can no longer be paid, e.g. when the order is cancelled
+    pub async fn cancel(self) -> Result<(), CraftgateError> {
+        self.client.expire_common_page_token(&self.token).await
+    }
+}
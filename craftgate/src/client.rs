@@ -1,31 +1,206 @@
 use core::fmt;
+#[cfg(feature = "payment")]
+use std::future::Future;
+#[cfg(feature = "reqwest")]
+use std::sync::Arc;
 
-use reqwest::{Response, Url};
-use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
-use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
-use reqwest_tracing::TracingMiddleware;
+#[cfg(feature = "reporting")]
+use bytes::Bytes;
 
+#[cfg(any(
+    feature = "onboarding",
+    feature = "payment",
+    feature = "settlement",
+    feature = "bank-account-tracking"
+))]
+use http::Method;
+use http::StatusCode;
+#[cfg(feature = "reqwest")]
+use reqwest_middleware::{ClientBuilder, Middleware};
+#[cfg(feature = "tracing")]
+use reqwest_tracing::TracingMiddleware;
+#[cfg(any(
+    feature = "onboarding",
+    feature = "payment",
+    feature = "settlement",
+    feature = "bank-account-tracking"
+))]
 use serde::Deserialize;
+#[cfg(feature = "reporting")]
+use time::Date;
+use time::UtcOffset;
+#[cfg(feature = "payment")]
+use time::{Duration, OffsetDateTime};
+#[cfg(feature = "reqwest")]
+use typed_builder::TypedBuilder;
+use url::Url;
 
-use crate::middleware::CraftgateSignatureMiddleware;
-use crate::request::onboarding::create_member::CreateMemberRequest;
-use crate::request::onboarding::search_members::SearchMembersRequest;
-use crate::request::onboarding::update_member::UpdateMemberRequest;
-use crate::request::onboarding::Member;
-use crate::request::payment::checkout_payment::{
-    CheckoutPaymentInitiationRequest, CheckoutPaymentInitiationResponse,
+use crate::api_error::{ErrorCode, ErrorGroup, ErrorResponse};
+#[cfg(feature = "bank-account-tracking")]
+use crate::id::BankAccountTrackingRecordId;
+#[cfg(any(feature = "onboarding", feature = "wallet"))]
+use crate::id::MemberId;
+#[cfg(feature = "settlement")]
+use crate::id::PayoutAccountId;
+#[cfg(feature = "payment")]
+use crate::id::{BkmExpressTicketId, CheckoutToken, PaymentId};
+#[cfg(feature = "payment")]
+use crate::idempotency::IdempotencyKey;
+#[cfg(feature = "bank-account-tracking")]
+use crate::request::bank_account_tracking::{
+    search_records::SearchBankAccountTrackingRecordsRequest, BankAccountTrackingRecord,
+};
+#[cfg(any(
+    feature = "onboarding",
+    feature = "payment",
+    feature = "settlement",
+    feature = "bank-account-tracking"
+))]
+use crate::request::date;
+#[cfg(feature = "onboarding")]
+use crate::request::onboarding::{
+    create_member::CreateMemberRequest, search_members::SearchMembersRequest,
+    update_member::UpdateMemberRequest, Member,
+};
+#[cfg(any(feature = "payment", feature = "wallet"))]
+use crate::request::payment::PaymentStatus;
+#[cfg(feature = "payment")]
+use crate::request::payment::{
+    apple_pay::{ApplePayMerchantSession, ApplePayMerchantSessionRequest},
+    bkm_express::{CompleteBkmExpressRequest, InitBkmExpressRequest, InitBkmExpressResponse},
+    checkout_payment::{CheckoutPaymentInitiationRequest, CheckoutPaymentInitiationResponse},
+    create_payment::CreatePaymentRequest,
+    garanti_pay::{InitGarantiPayPaymentRequest, InitGarantiPayPaymentResponse},
+    masterpass::{
+        CheckMasterpassUserRequest, CheckMasterpassUserResponse, MasterpassPaymentCompleteRequest,
+        MasterpassPaymentThreeDSInitRequest, MasterpassPaymentThreeDSInitResponse,
+        MasterpassPaymentTokenGenerateRequest, MasterpassPaymentTokenGenerateResponse,
+        MasterpassRetrieveLoyaltiesRequest,
+    },
+    multi_payment::MultiPayment,
+    retrieve_loyalties::{RetrieveLoyaltiesRequest, RetrieveLoyaltiesResponse},
+    search_payments::SearchPaymentsRequest,
+    three_ds_payment::{
+        CompleteThreeDSPaymentRequest, InitThreeDSPaymentRequest, InitThreeDSPaymentResponse,
+    },
+    transaction_refund::{PaymentTransactionRefund, SearchPaymentTransactionRefundsRequest},
+    Payment,
 };
-use crate::request::payment::Payment;
-use crate::response::{ApiResponseVariant, PaginatedResponse};
-use crate::{
-    api_error::{ErrorCode, ErrorGroup, ErrorResponse},
-    response::{ApiResponse, SuccessResponse},
+#[cfg(feature = "reporting")]
+use crate::request::reporting::{DailyReportQuery, ReportFileType};
+#[cfg(feature = "settlement")]
+use crate::request::settlement::{
+    instant_wallet_settlement::{CreateInstantWalletSettlementRequest, SettlementResponse},
+    payout_account::{
+        CreatePayoutAccountRequest, CreatePayoutAccountResponse, PayoutAccount,
+        SearchPayoutAccountsRequest, UpdatePayoutAccountRequest,
+    },
+    payout_completed_transaction::{
+        PayoutCompletedTransaction, SearchPayoutCompletedTransactionsRequest,
+    },
+};
+#[cfg(feature = "payment")]
+use crate::request::validation::Validate;
+use crate::request::validation::Violation;
+#[cfg(feature = "wallet")]
+use crate::request::wallet::{
+    deposit_payment::{
+        CreateDepositPaymentRequest, DepositPayment, InitCheckoutDepositPaymentRequest,
+        InitThreeDSDepositPaymentRequest,
+    },
+    fund_transfer_deposit::{CreateFundTransferDepositPaymentRequest, FundTransferDepositPayment},
+    Wallet,
 };
+#[cfg(any(
+    feature = "onboarding",
+    feature = "payment",
+    feature = "settlement",
+    feature = "bank-account-tracking"
+))]
+use crate::response::{ApiResponse, ApiResponseVariant, PaginatedResponse, SuccessResponse};
+#[cfg(feature = "retry")]
+use crate::retry::RetryMiddleware;
+#[cfg(feature = "reqwest")]
+use crate::retry::RetryPolicy;
+use crate::signature::{Credentials, SignatureCalculationError};
+#[cfg(feature = "reqwest")]
+use crate::transport::ReqwestTransport;
+#[cfg(any(
+    feature = "onboarding",
+    feature = "payment",
+    feature = "settlement",
+    feature = "bank-account-tracking"
+))]
+use crate::transport::{HttpRequest, HttpResponse, Operation};
+use crate::transport::{RequestEncodingError, Transport, TransportError};
 
+pub const PRODUCTION_BASE_URL: &str = "https://api.craftgate.io";
+pub const SANDBOX_BASE_URL: &str = "https://sandbox-api.craftgate.io";
+
+/// How many times [`CraftgateClient::execute_idempotent`] sends a request whose outcome could not
+/// be determined.
+#[cfg(feature = "payment")]
+const MAX_IDEMPOTENT_ATTEMPTS: usize = 3;
+
+/// Client for the Craftgate API. Requests are sent with `reqwest` unless another [`Transport`] is
+/// given to [`CraftgateClient::new_with_transport`].
+#[cfg(feature = "reqwest")]
 #[derive(Clone)]
-pub struct CraftgateClient {
-    client: ClientWithMiddleware,
+// Only read by the requests of the API area features.
+#[cfg_attr(
+    not(any(
+        feature = "onboarding",
+        feature = "payment",
+        feature = "settlement",
+        feature = "bank-account-tracking"
+    )),
+    allow(dead_code)
+)]
+pub struct CraftgateClient<T = ReqwestTransport> {
+    transport: T,
+    credentials: Credentials,
     base_url: Url,
+    utc_offset: Option<UtcOffset>,
+}
+
+/// Client for the Craftgate API, sending requests with the [`Transport`] given to
+/// [`CraftgateClient::new_with_transport`].
+#[cfg(not(feature = "reqwest"))]
+#[derive(Clone)]
+// Only read by the requests of the API area features.
+#[cfg_attr(
+    not(any(
+        feature = "onboarding",
+        feature = "payment",
+        feature = "settlement",
+        feature = "bank-account-tracking"
+    )),
+    allow(dead_code)
+)]
+pub struct CraftgateClient<T> {
+    transport: T,
+    credentials: Credentials,
+    base_url: Url,
+    utc_offset: Option<UtcOffset>,
+}
+
+#[cfg(feature = "reqwest")]
+#[derive(Clone, Default, TypedBuilder)]
+#[builder(field_defaults(default))]
+pub struct ClientOptions {
+    /// Send requests to the sandbox environment
+    pub sandbox: bool,
+    /// Send requests to this url instead of the Craftgate API, e.g. a mock server in tests
+    pub base_url: Option<Url>,
+    /// Retry policy for failed requests. Only safe lookups are retried on errors that may have
+    /// reached Craftgate, see `RetryMiddleware`. Ignored without the `retry` feature.
+    pub retry_policy: RetryPolicy,
+    /// Additional middleware, run in order before the request is sent. Requests are already
+    /// signed at this point.
+    pub middleware: Vec<Arc<dyn Middleware>>,
+    /// Offset Craftgate timestamps of the merchant are read and sent in, `DEFAULT_OFFSET`
+    /// (`+03:00`) unless set
+    pub utc_offset: Option<UtcOffset>,
 }
 
 #[derive(Debug)]
@@ -48,11 +223,43 @@ pub enum CraftgateError {
     UnexpectedFormat {
         expected: ResponseFormat,
     },
+    /// The request was rejected before being sent, see [`Validate`]
+    InvalidRequest(Vec<Violation>),
+    /// Craftgate responded with an error status and no error in the body
+    HttpStatus(StatusCode),
     SerdeError(serde_json::Error),
+    RequestEncoding(RequestEncodingError),
+    Transport(TransportError),
+    #[cfg(feature = "reqwest")]
     ReqwestError(reqwest::Error),
+    #[cfg(feature = "reqwest")]
     ReqwestMiddlewareError(reqwest_middleware::Error),
+    SignatureError(SignatureCalculationError),
+    /// More than one payment matched a lookup that expects at most one, see
+    /// [`CraftgateClient::find_payment`]
+    #[cfg(feature = "payment")]
+    AmbiguousPayment(Vec<PaymentId>),
+}
+
+impl From<SignatureCalculationError> for CraftgateError {
+    fn from(value: SignatureCalculationError) -> Self {
+        Self::SignatureError(value)
+    }
+}
+
+impl From<RequestEncodingError> for CraftgateError {
+    fn from(value: RequestEncodingError) -> Self {
+        Self::RequestEncoding(value)
+    }
 }
 
+impl From<TransportError> for CraftgateError {
+    fn from(value: TransportError) -> Self {
+        Self::Transport(value)
+    }
+}
+
+#[cfg(feature = "reqwest")]
 impl From<reqwest_middleware::Error> for CraftgateError {
     fn from(value: reqwest_middleware::Error) -> Self {
         Self::ReqwestMiddlewareError(value)
@@ -65,6 +272,7 @@ impl From<serde_json::Error> for CraftgateError {
     }
 }
 
+#[cfg(feature = "reqwest")]
 impl From<reqwest::Error> for CraftgateError {
     fn from(value: reqwest::Error) -> Self {
         Self::ReqwestError(value)
@@ -88,6 +296,48 @@ impl From<ErrorResponse> for CraftgateError {
     }
 }
 
+impl CraftgateError {
+    /// Whether the request may have been processed by Craftgate or the bank even though it failed,
+    /// e.g. a timeout after the request was sent or a bank communication error. Such requests must
+    /// be reconciled before being sent again.
+    pub fn is_ambiguous(&self) -> bool {
+        match self {
+            CraftgateError::PaymentError { group, .. } => matches!(
+                group,
+                ErrorGroup::CommunicationOrSystemError
+                    | ErrorGroup::RequestTimeout
+                    | ErrorGroup::NoResponse
+            ),
+            CraftgateError::HttpStatus(status) => status.is_server_error(),
+            CraftgateError::SerdeError(_) => true,
+            // Nothing is known about where a custom transport failed.
+            CraftgateError::Transport(_) => true,
+            #[cfg(feature = "reqwest")]
+            CraftgateError::ReqwestError(e) => is_ambiguous_reqwest_error(e),
+            #[cfg(feature = "reqwest")]
+            CraftgateError::ReqwestMiddlewareError(reqwest_middleware::Error::Reqwest(e)) => {
+                is_ambiguous_reqwest_error(e)
+            }
+            #[cfg(feature = "reqwest")]
+            CraftgateError::ReqwestMiddlewareError(_) => false,
+            CraftgateError::ValidationError { .. }
+            | CraftgateError::UnexpectedFormat { .. }
+            | CraftgateError::InvalidRequest(_)
+            | CraftgateError::RequestEncoding(_)
+            | CraftgateError::SignatureError(_) => false,
+            #[cfg(feature = "payment")]
+            CraftgateError::AmbiguousPayment(_) => false,
+        }
+    }
+}
+
+/// A request that failed before a connection was made never reached Craftgate. Anything failing
+/// after that point may have been processed.
+#[cfg(feature = "reqwest")]
+fn is_ambiguous_reqwest_error(e: &reqwest::Error) -> bool {
+    !e.is_builder() && !e.is_connect()
+}
+
 impl fmt::Display for CraftgateError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -106,196 +356,1125 @@ impl fmt::Display for CraftgateError {
             CraftgateError::UnexpectedFormat { expected } => {
                 write!(f, "Unexpected response format. Expected: {:?}", expected)
             }
+            CraftgateError::InvalidRequest(violations) => {
+                write!(f, "Invalid request: ")?;
+                for (i, violation) in violations.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", violation)?;
+                }
+                Ok(())
+            }
+            CraftgateError::HttpStatus(status) => write!(f, "Unexpected HTTP status: {}", status),
             CraftgateError::SerdeError(e) => write!(f, "Serde Error: {}", e),
+            CraftgateError::RequestEncoding(e) => write!(f, "Request Encoding Error: {}", e),
+            CraftgateError::Transport(e) => write!(f, "Transport Error: {}", e),
+            #[cfg(feature = "reqwest")]
             CraftgateError::ReqwestError(e) => write!(f, "Reqwest Error: {}", e),
+            #[cfg(feature = "reqwest")]
             CraftgateError::ReqwestMiddlewareError(e) => {
                 write!(f, "Reqwest Middleware Error: {}", e)
             }
+            CraftgateError::SignatureError(e) => write!(f, "Signature Error: {}", e),
+            #[cfg(feature = "payment")]
+            CraftgateError::AmbiguousPayment(ids) => {
+                write!(f, "More than one payment matched: {:?}", ids)
+            }
         }
     }
 }
 
+/// Url of the production or sandbox API
+#[cfg(feature = "reqwest")]
+pub(crate) fn base_url(sandbox: bool) -> Url {
+    if sandbox {
+        Url::parse(SANDBOX_BASE_URL).unwrap()
+    } else {
+        Url::parse(PRODUCTION_BASE_URL).unwrap()
+    }
+}
+
+#[cfg(feature = "reqwest")]
 impl CraftgateClient {
     pub fn new(sandbox: bool, api_key: &str, secret_key: &str) -> Self {
-        let retry_policy = ExponentialBackoff::builder().build_with_max_retries(3);
-        let client = ClientBuilder::new(reqwest::Client::new())
-            // Trace HTTP requests. See the tracing crate to make use of these traces.
-            .with(TracingMiddleware::default())
-            // Retry failed requests.
-            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
-            .with(CraftgateSignatureMiddleware::new_with_keys(
-                secret_key, api_key,
-            ))
-            .build();
+        Self::new_with_options(
+            api_key,
+            secret_key,
+            ClientOptions::builder().sandbox(sandbox).build(),
+        )
+    }
 
+    pub fn new_with_options(api_key: &str, secret_key: &str, options: ClientOptions) -> Self {
+        let builder = ClientBuilder::new(reqwest::Client::new());
+        // Trace HTTP requests. See the tracing crate to make use of these traces.
+        #[cfg(feature = "tracing")]
+        let builder = builder.with(TracingMiddleware::default());
+        // Retry failed requests depending on the `Operation` attached to them.
+        #[cfg(feature = "retry")]
+        let builder = builder.with(RetryMiddleware::new_with_policy(options.retry_policy));
+        let mut builder = builder;
+        for middleware in options.middleware {
+            builder = builder.with_arc(middleware);
+        }
+
+        let client = Self::new_with_transport(
+            api_key,
+            secret_key,
+            options
+                .base_url
+                .unwrap_or_else(|| base_url(options.sandbox)),
+            ReqwestTransport::new(builder.build()),
+        );
+
+        match options.utc_offset {
+            Some(offset) => client.with_utc_offset(offset),
+            None => client,
+        }
+    }
+}
+
+impl<T: Transport> CraftgateClient<T> {
+    /// Sends requests to `base_url` with `transport`. Use [`PRODUCTION_BASE_URL`] or
+    /// [`SANDBOX_BASE_URL`] for the Craftgate API.
+    pub fn new_with_transport(
+        api_key: &str,
+        secret_key: &str,
+        base_url: Url,
+        transport: T,
+    ) -> Self {
         Self {
-            client,
-            base_url: Self::url(sandbox),
+            transport,
+            credentials: Credentials::new(api_key, secret_key),
+            base_url,
+            utc_offset: None,
         }
     }
 
-    fn url(sandbox: bool) -> Url {
-        if sandbox {
-            Url::parse("https://sandbox-api.craftgate.io").unwrap()
-        } else {
-            Url::parse("https://api.craftgate.io").unwrap()
+    /// Reads and sends timestamps in `offset` instead of `DEFAULT_OFFSET` (`+03:00`)
+    pub fn with_utc_offset(self, offset: UtcOffset) -> Self {
+        Self {
+            utc_offset: Some(offset),
+            ..self
         }
     }
 
+    #[cfg(any(
+        feature = "onboarding",
+        feature = "payment",
+        feature = "settlement",
+        feature = "bank-account-tracking"
+    ))]
+    fn utc_offset(&self) -> UtcOffset {
+        self.utc_offset.unwrap_or(date::DEFAULT_OFFSET)
+    }
+
+    #[cfg(any(
+        feature = "onboarding",
+        feature = "payment",
+        feature = "settlement",
+        feature = "bank-account-tracking"
+    ))]
+    fn request(&self, method: Method, path: &str, operation: Operation) -> HttpRequest {
+        HttpRequest::new(
+            method,
+            self.base_url.join(path).expect("valid url"),
+            operation,
+        )
+    }
+
+    #[cfg(any(
+        feature = "onboarding",
+        feature = "payment",
+        feature = "settlement",
+        feature = "bank-account-tracking"
+    ))]
+    /// Signs `request` and sends it with the transport
+    async fn send(&self, mut request: HttpRequest) -> Result<HttpResponse, CraftgateError> {
+        self.credentials.sign(&mut request)?;
+
+        self.transport.send(request).await.map_err(Into::into)
+    }
+
+    #[cfg(feature = "onboarding")]
     pub async fn create_member(
         &self,
         member: CreateMemberRequest,
     ) -> Result<Member, CraftgateError> {
-        let resp = self
-            .client
-            .post(
-                self.base_url
-                    .join("/onboarding/v1/members")
-                    .expect("valid url"),
-            )
-            .json(&member)
-            .send()
-            .await?;
+        let request = self
+            .request(Method::POST, "/onboarding/v1/members", Operation::Mutation)
+            .json(&member, self.utc_offset())?;
+        let resp = self.send(request).await?;
 
-        let member: Member = extract_single_response(resp).await?;
+        let member: Member = decode_single_response(&resp.body, self.utc_offset())?;
 
         Ok(member)
     }
 
+    #[cfg(feature = "onboarding")]
     pub async fn update_member(
         &self,
-        member_id: u64,
+        member_id: MemberId,
         member: UpdateMemberRequest,
     ) -> Result<Member, CraftgateError> {
-        let resp = self
-            .client
-            .put(
-                self.base_url
-                    .join(&format!("/onboarding/v1/members/{}", member_id))
-                    .expect("valid_url"),
+        let request = self
+            .request(
+                Method::PUT,
+                &format!("/onboarding/v1/members/{}", member_id),
+                Operation::Mutation,
             )
-            .json(&member)
-            .send()
-            .await?;
+            .json(&member, self.utc_offset())?;
+        let resp = self.send(request).await?;
 
-        let member: Member = extract_single_response(resp).await?;
+        let member: Member = decode_single_response(&resp.body, self.utc_offset())?;
 
         Ok(member)
     }
 
-    pub async fn retrieve_member(&self, member_id: u64) -> Result<Option<Member>, CraftgateError> {
-        let resp = self
-            .client
-            .get(
-                self.base_url
-                    .join(&format!("/onboarding/v1/members/{}", member_id))
-                    .expect("valid_url"),
-            )
-            .send()
-            .await?;
+    #[cfg(feature = "onboarding")]
+    pub async fn retrieve_member(
+        &self,
+        member_id: MemberId,
+    ) -> Result<Option<Member>, CraftgateError> {
+        let request = self.request(
+            Method::GET,
+            &format!("/onboarding/v1/members/{}", member_id),
+            Operation::Safe,
+        );
+        let resp = self.send(request).await?;
 
-        let member: Option<Member> = extract_single_response(resp).await?;
+        let member: Option<Member> = decode_single_response(&resp.body, self.utc_offset())?;
 
         Ok(member)
     }
 
+    #[cfg(feature = "onboarding")]
     pub async fn search_members(
         &self,
         params: SearchMembersRequest,
     ) -> Result<PaginatedResponse<Member>, CraftgateError> {
-        let resp = self
-            .client
-            .get(
-                self.base_url
-                    .join("/onboarding/v1/members")
-                    .expect("valid url"),
-            )
-            .query(&params)
-            .send()
-            .await?;
+        let request = self
+            .request(Method::GET, "/onboarding/v1/members", Operation::Safe)
+            .query(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
 
-        let members: PaginatedResponse<Member> = extract_paginated_response(resp).await?;
+        let members: PaginatedResponse<Member> =
+            decode_paginated_response(&resp.body, self.utc_offset())?;
 
         Ok(members)
     }
 
+    /// Validates `params` before sending them, see [`Validate`]. Use
+    /// [`initiate_checkout_payment_unchecked`](Self::initiate_checkout_payment_unchecked) to leave
+    /// validation to Craftgate.
+    #[cfg(feature = "payment")]
     pub async fn initiate_checkout_payment(
         &self,
         params: CheckoutPaymentInitiationRequest,
     ) -> Result<CheckoutPaymentInitiationResponse, CraftgateError> {
-        let resp = self
-            .client
-            .post(
-                self.base_url
-                    .join("/payment/v1/checkout-payments/init")
-                    .expect("valid url"),
+        params.validate().map_err(CraftgateError::InvalidRequest)?;
+
+        self.initiate_checkout_payment_unchecked(params).await
+    }
+
+    #[cfg(feature = "payment")]
+    pub async fn initiate_checkout_payment_unchecked(
+        &self,
+        params: CheckoutPaymentInitiationRequest,
+    ) -> Result<CheckoutPaymentInitiationResponse, CraftgateError> {
+        let request = self
+            .request(
+                Method::POST,
+                "/payment/v1/checkout-payments/init",
+                Operation::Mutation,
             )
-            .json(&params)
-            .send()
-            .await?;
+            .json(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
 
-        let resp: CheckoutPaymentInitiationResponse = extract_single_response(resp).await?;
+        let resp: CheckoutPaymentInitiationResponse =
+            decode_single_response(&resp.body, self.utc_offset())?;
 
         Ok(resp)
     }
 
-    pub async fn checkout_payment_inquiry(&self, token: String) -> Result<Payment, CraftgateError> {
-        let resp = self
-            .client
-            .get(
-                self.base_url
-                    .join(&format!("/payment/v1/checkout-payments/{}", token))
-                    .expect("valid url"),
+    #[cfg(feature = "payment")]
+    pub async fn checkout_payment_inquiry(
+        &self,
+        token: &CheckoutToken,
+    ) -> Result<Payment, CraftgateError> {
+        let request = self.request(
+            Method::GET,
+            &format!("/payment/v1/checkout-payments/{}", token),
+            Operation::Safe,
+        );
+        let resp = self.send(request).await?;
+
+        let payment: Payment = decode_single_response(&resp.body, self.utc_offset())?;
+
+        Ok(payment)
+    }
+
+    #[cfg(feature = "payment")]
+    pub async fn expire_common_page_token(
+        &self,
+        token: &CheckoutToken,
+    ) -> Result<(), CraftgateError> {
+        let request = self.request(
+            Method::DELETE,
+            &format!("/payment/v1/checkout-payments/{}", token),
+            Operation::Mutation,
+        );
+        let resp = self.send(request).await?;
+
+        decode_empty_response(&resp)
+    }
+
+    /// Retrieves the payments made so far on a checkout form that allows multi-payment
+    #[cfg(feature = "payment")]
+    pub async fn retrieve_multi_payment(
+        &self,
+        token: &CheckoutToken,
+    ) -> Result<MultiPayment, CraftgateError> {
+        let request = self.request(
+            Method::GET,
+            &format!("/payment/v1/multi-payments/{}", token),
+            Operation::Safe,
+        );
+        let resp = self.send(request).await?;
+
+        decode_single_response(&resp.body, self.utc_offset())
+    }
+
+    #[cfg(feature = "payment")]
+    pub async fn retrieve_payment(&self, id: PaymentId) -> Result<Payment, CraftgateError> {
+        let request = self.request(
+            Method::GET,
+            &format!("/payment/v1/card-payments/{}", id),
+            Operation::Safe,
+        );
+        let resp = self.send(request).await?;
+
+        let payment: Payment = decode_single_response(&resp.body, self.utc_offset())?;
+
+        Ok(payment)
+    }
+
+    #[cfg(feature = "payment")]
+    pub async fn search_payments(
+        &self,
+        params: SearchPaymentsRequest,
+    ) -> Result<PaginatedResponse<Payment>, CraftgateError> {
+        let request = self
+            .request(Method::GET, "/payment/v1/card-payments", Operation::Safe)
+            .query(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
+
+        let payments: PaginatedResponse<Payment> =
+            decode_paginated_response(&resp.body, self.utc_offset())?;
+
+        Ok(payments)
+    }
+
+    #[cfg(feature = "payment")]
+    pub async fn search_payment_transaction_refunds(
+        &self,
+        params: SearchPaymentTransactionRefundsRequest,
+    ) -> Result<PaginatedResponse<PaymentTransactionRefund>, CraftgateError> {
+        let request = self
+            .request(
+                Method::GET,
+                "/payment/v1/refund-transactions",
+                Operation::Safe,
+            )
+            .query(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
+
+        let refunds: PaginatedResponse<PaymentTransactionRefund> =
+            decode_paginated_response(&resp.body, self.utc_offset())?;
+
+        Ok(refunds)
+    }
+
+    /// Looks up the reward points available on a card, to be used in a payment by setting the
+    /// [`Loyalty`](crate::request::payment::Loyalty) on its card
+    #[cfg(feature = "payment")]
+    pub async fn retrieve_loyalties(
+        &self,
+        params: RetrieveLoyaltiesRequest,
+    ) -> Result<RetrieveLoyaltiesResponse, CraftgateError> {
+        let request = self
+            .request(
+                Method::POST,
+                "/payment/v1/card-loyalties/retrieve",
+                Operation::Safe,
+            )
+            .json(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
+
+        let loyalties: RetrieveLoyaltiesResponse =
+            decode_single_response(&resp.body, self.utc_offset())?;
+
+        Ok(loyalties)
+    }
+
+    /// Creates a card payment. A payment whose outcome is unknown after an ambiguous failure (see
+    /// [`CraftgateClient::execute_idempotent`]) is looked up by its `external_id`, which must be
+    /// unique to the payment, and only sent again when Craftgate has no payment with that id and
+    /// the same prices. Without an `external_id` the payment is sent only once.
+    #[cfg(feature = "payment")]
+    pub async fn create_payment(
+        &self,
+        params: CreatePaymentRequest,
+        key: &IdempotencyKey,
+    ) -> Result<Payment, CraftgateError> {
+        let send = || {
+            let params = &params;
+            async move {
+                let request = self
+                    .request(
+                        Method::POST,
+                        "/payment/v1/card-payments",
+                        Operation::Mutation,
+                    )
+                    .json(params, self.utc_offset())?;
+                let resp = self.send(request).await?;
+
+                decode_single_response(&resp.body, self.utc_offset())
+            }
+        };
+
+        // A conversation id may be reused by the merchant, only the external id identifies the
+        // payment.
+        let Some(external_id) = params.external_id.as_deref() else {
+            return send().await;
+        };
+
+        // Craftgate's clock may be slightly behind ours.
+        let created_since = OffsetDateTime::now_utc() - Duration::minutes(5);
+        self.execute_idempotent(key, send, |_| async {
+            let payment = self
+                .find_payment(None, Some(external_id), Some(created_since))
+                .await?;
+
+            Ok(payment.filter(|payment| {
+                payment.price == params.price
+                    && payment.paid_price == params.paid_price
+                    && payment.currency == params.currency
+            }))
+        })
+        .await
+    }
+
+    #[cfg(feature = "payment")]
+    pub async fn init_3ds_payment(
+        &self,
+        params: InitThreeDSPaymentRequest,
+    ) -> Result<InitThreeDSPaymentResponse, CraftgateError> {
+        let request = self
+            .request(
+                Method::POST,
+                "/payment/v1/card-payments/3ds-init",
+                Operation::Mutation,
             )
-            .send()
-            .await?;
+            .json(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
+
+        let resp: InitThreeDSPaymentResponse =
+            decode_single_response(&resp.body, self.utc_offset())?;
+
+        Ok(resp)
+    }
+
+    /// Completes a 3D Secure payment after the buyer returned to the callback URL. After an
+    /// ambiguous failure the completion is only sent again while the payment still waits for it
+    /// ([`PaymentStatus::CallbackThreeds`]). In any other status than success the outcome of the
+    /// completion is unknown and the original error is returned.
+    #[cfg(feature = "payment")]
+    pub async fn complete_3ds_payment(
+        &self,
+        payment_id: PaymentId,
+        key: &IdempotencyKey,
+    ) -> Result<Payment, CraftgateError> {
+        let send = || async move {
+            let request = self
+                .request(
+                    Method::POST,
+                    "/payment/v1/card-payments/3ds-complete",
+                    Operation::Mutation,
+                )
+                .json(
+                    &CompleteThreeDSPaymentRequest { payment_id },
+                    self.utc_offset(),
+                )?;
+            let resp = self.send(request).await?;
+
+            decode_single_response(&resp.body, self.utc_offset())
+        };
+
+        self.execute_idempotent(key, send, |err| async {
+            let payment = self.retrieve_payment(payment_id).await?;
+            match payment.payment_status {
+                PaymentStatus::Success => Ok(Some(payment)),
+                PaymentStatus::CallbackThreeds => Ok(None),
+                _ => Err(err),
+            }
+        })
+        .await
+    }
+
+    #[cfg(feature = "wallet")]
+    pub async fn retrieve_member_wallet(
+        &self,
+        member_id: MemberId,
+    ) -> Result<Wallet, CraftgateError> {
+        let request = self.request(
+            Method::GET,
+            &format!("/wallet/v1/members/{}/wallet", member_id),
+            Operation::Safe,
+        );
+        let resp = self.send(request).await?;
+
+        let wallet: Wallet = decode_single_response(&resp.body, self.utc_offset())?;
+
+        Ok(wallet)
+    }
+
+    /// Tops up the wallet of the buyer from a card. Craftgate does not return the new balance,
+    /// [`DepositPayment::wallet_transaction`] only holds the credit of a successful deposit. The
+    /// balance is returned by [`retrieve_member_wallet`](Self::retrieve_member_wallet).
+    #[cfg(feature = "wallet")]
+    pub async fn create_deposit_payment(
+        &self,
+        params: CreateDepositPaymentRequest,
+    ) -> Result<DepositPayment, CraftgateError> {
+        let request = self
+            .request(Method::POST, "/payment/v1/deposits", Operation::Mutation)
+            .json(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
 
-        // let payment: Payment = extract_single_response(resp).await?;
+        let deposit: DepositPayment = decode_single_response(&resp.body, self.utc_offset())?;
+
+        Ok(deposit)
+    }
+
+    #[cfg(feature = "wallet")]
+    pub async fn init_3ds_deposit_payment(
+        &self,
+        params: InitThreeDSDepositPaymentRequest,
+    ) -> Result<InitThreeDSPaymentResponse, CraftgateError> {
+        let request = self
+            .request(
+                Method::POST,
+                "/payment/v1/deposits/3ds-init",
+                Operation::Mutation,
+            )
+            .json(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
+
+        let resp: InitThreeDSPaymentResponse =
+            decode_single_response(&resp.body, self.utc_offset())?;
+
+        Ok(resp)
+    }
+
+    /// Completes a 3D Secure deposit after the buyer returned to the callback URL
+    #[cfg(feature = "wallet")]
+    pub async fn complete_3ds_deposit_payment(
+        &self,
+        payment_id: PaymentId,
+    ) -> Result<DepositPayment, CraftgateError> {
+        let request = self
+            .request(
+                Method::POST,
+                "/payment/v1/deposits/3ds-complete",
+                Operation::Mutation,
+            )
+            .json(
+                &CompleteThreeDSPaymentRequest { payment_id },
+                self.utc_offset(),
+            )?;
+        let resp = self.send(request).await?;
+
+        let deposit: DepositPayment = decode_single_response(&resp.body, self.utc_offset())?;
+
+        Ok(deposit)
+    }
+
+    /// Initiates a checkout form for a deposit. The result is inquired with
+    /// [`checkout_payment_inquiry`](Self::checkout_payment_inquiry) like other checkout payments.
+    #[cfg(feature = "wallet")]
+    pub async fn init_checkout_deposit_payment(
+        &self,
+        params: InitCheckoutDepositPaymentRequest,
+    ) -> Result<CheckoutPaymentInitiationResponse, CraftgateError> {
+        let request = self
+            .request(
+                Method::POST,
+                "/payment/v1/deposits/checkout-init",
+                Operation::Mutation,
+            )
+            .json(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
+
+        let resp: CheckoutPaymentInitiationResponse =
+            decode_single_response(&resp.body, self.utc_offset())?;
+
+        Ok(resp)
+    }
+
+    #[cfg(feature = "wallet")]
+    pub async fn create_fund_transfer_deposit_payment(
+        &self,
+        params: CreateFundTransferDepositPaymentRequest,
+    ) -> Result<FundTransferDepositPayment, CraftgateError> {
+        let request = self
+            .request(
+                Method::POST,
+                "/payment/v1/deposits/fund-transfer",
+                Operation::Mutation,
+            )
+            .json(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
+
+        let deposit: FundTransferDepositPayment =
+            decode_single_response(&resp.body, self.utc_offset())?;
+
+        Ok(deposit)
+    }
+
+    /// Settles the wallets of sub-merchants now, except `excluded_sub_merchant_member_ids`
+    #[cfg(feature = "settlement")]
+    pub async fn create_instant_wallet_settlement(
+        &self,
+        params: CreateInstantWalletSettlementRequest,
+    ) -> Result<SettlementResponse, CraftgateError> {
+        let request = self
+            .request(
+                Method::POST,
+                "/settlement/v1/instant-wallet-settlements",
+                Operation::Mutation,
+            )
+            .json(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
+
+        let settlement: SettlementResponse = decode_single_response(&resp.body, self.utc_offset())?;
+
+        Ok(settlement)
+    }
+
+    #[cfg(feature = "settlement")]
+    pub async fn create_payout_account(
+        &self,
+        params: CreatePayoutAccountRequest,
+    ) -> Result<CreatePayoutAccountResponse, CraftgateError> {
+        let request = self
+            .request(
+                Method::POST,
+                "/settlement/v1/payout-accounts",
+                Operation::Mutation,
+            )
+            .json(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
+
+        let account: CreatePayoutAccountResponse =
+            decode_single_response(&resp.body, self.utc_offset())?;
+
+        Ok(account)
+    }
+
+    #[cfg(feature = "settlement")]
+    pub async fn update_payout_account(
+        &self,
+        id: PayoutAccountId,
+        params: UpdatePayoutAccountRequest,
+    ) -> Result<(), CraftgateError> {
+        let request = self
+            .request(
+                Method::PUT,
+                &format!("/settlement/v1/payout-accounts/{}", id),
+                Operation::Mutation,
+            )
+            .json(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
+
+        decode_empty_response(&resp)
+    }
+
+    #[cfg(feature = "settlement")]
+    pub async fn delete_payout_account(&self, id: PayoutAccountId) -> Result<(), CraftgateError> {
+        let request = self.request(
+            Method::DELETE,
+            &format!("/settlement/v1/payout-accounts/{}", id),
+            Operation::Mutation,
+        );
+        let resp = self.send(request).await?;
+
+        decode_empty_response(&resp)
+    }
+
+    #[cfg(feature = "settlement")]
+    pub async fn search_payout_accounts(
+        &self,
+        params: SearchPayoutAccountsRequest,
+    ) -> Result<PaginatedResponse<PayoutAccount>, CraftgateError> {
+        let request = self
+            .request(
+                Method::GET,
+                "/settlement/v1/payout-accounts",
+                Operation::Safe,
+            )
+            .query(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
+
+        let accounts: PaginatedResponse<PayoutAccount> =
+            decode_paginated_response(&resp.body, self.utc_offset())?;
+
+        Ok(accounts)
+    }
+
+    /// Searches the payment transactions paid out to the merchant and sub-merchants
+    #[cfg(feature = "settlement")]
+    pub async fn search_payout_completed_transactions(
+        &self,
+        params: SearchPayoutCompletedTransactionsRequest,
+    ) -> Result<PaginatedResponse<PayoutCompletedTransaction>, CraftgateError> {
+        let request = self
+            .request(
+                Method::GET,
+                "/settlement-reporting/v1/settlement-file/payout-completed-transactions",
+                Operation::Safe,
+            )
+            .query(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
+
+        let transactions: PaginatedResponse<PayoutCompletedTransaction> =
+            decode_paginated_response(&resp.body, self.utc_offset())?;
+
+        Ok(transactions)
+    }
+
+    /// Searches incoming and outgoing transfers on the merchant's tracked bank accounts
+    #[cfg(feature = "bank-account-tracking")]
+    pub async fn search_bank_account_tracking_records(
+        &self,
+        params: SearchBankAccountTrackingRecordsRequest,
+    ) -> Result<PaginatedResponse<BankAccountTrackingRecord>, CraftgateError> {
+        let request = self
+            .request(
+                Method::GET,
+                "/bank-account-tracking/v1/merchant-bank-account-trackings/records",
+                Operation::Safe,
+            )
+            .query(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
+
+        let records: PaginatedResponse<BankAccountTrackingRecord> =
+            decode_paginated_response(&resp.body, self.utc_offset())?;
+
+        Ok(records)
+    }
+
+    #[cfg(feature = "bank-account-tracking")]
+    pub async fn retrieve_bank_account_tracking_record(
+        &self,
+        id: BankAccountTrackingRecordId,
+    ) -> Result<BankAccountTrackingRecord, CraftgateError> {
+        let request = self.request(
+            Method::GET,
+            &format!(
+                "/bank-account-tracking/v1/merchant-bank-account-trackings/records/{}",
+                id
+            ),
+            Operation::Safe,
+        );
+        let resp = self.send(request).await?;
+
+        let record: BankAccountTrackingRecord =
+            decode_single_response(&resp.body, self.utc_offset())?;
+
+        Ok(record)
+    }
+
+    /// Downloads the report of the payment transactions of `date`. A CSV report can be read with
+    /// [`parse_transaction_report`](crate::request::reporting::transaction_report::parse_transaction_report).
+    #[cfg(feature = "reporting")]
+    pub async fn retrieve_daily_transaction_report(
+        &self,
+        date: Date,
+        file_type: ReportFileType,
+    ) -> Result<Bytes, CraftgateError> {
+        self.retrieve_daily_report("/file-reporting/v1/transaction-reports", date, file_type)
+            .await
+    }
+
+    /// Downloads the report of the payments of `date`
+    #[cfg(feature = "reporting")]
+    pub async fn retrieve_daily_payment_report(
+        &self,
+        date: Date,
+        file_type: ReportFileType,
+    ) -> Result<Bytes, CraftgateError> {
+        self.retrieve_daily_report("/file-reporting/v1/payment-reports", date, file_type)
+            .await
+    }
+
+    #[cfg(feature = "reporting")]
+    async fn retrieve_daily_report(
+        &self,
+        path: &str,
+        date: Date,
+        file_type: ReportFileType,
+    ) -> Result<Bytes, CraftgateError> {
+        let request = self.request(Method::GET, path, Operation::Safe).query(
+            &DailyReportQuery {
+                report_date: date,
+                file_type,
+            },
+            self.utc_offset(),
+        )?;
+        let resp = self.send(request).await?;
+
+        decode_file_response(resp)
+    }
+
+    /// Checks whether the buyer with the given phone number can pay with Masterpass
+    #[cfg(feature = "payment")]
+    pub async fn check_masterpass_user(
+        &self,
+        params: CheckMasterpassUserRequest,
+    ) -> Result<CheckMasterpassUserResponse, CraftgateError> {
+        let request = self
+            .request(
+                Method::POST,
+                "/payment/v1/masterpass-payments/check-user",
+                Operation::Safe,
+            )
+            .json(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
+
+        let user: CheckMasterpassUserResponse =
+            decode_single_response(&resp.body, self.utc_offset())?;
+
+        Ok(user)
+    }
+
+    #[cfg(feature = "payment")]
+    pub async fn generate_masterpass_payment_token(
+        &self,
+        params: MasterpassPaymentTokenGenerateRequest,
+    ) -> Result<MasterpassPaymentTokenGenerateResponse, CraftgateError> {
+        let request = self
+            .request(
+                Method::POST,
+                "/payment/v1/masterpass-payments/generate-token",
+                Operation::Mutation,
+            )
+            .json(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
+
+        let token: MasterpassPaymentTokenGenerateResponse =
+            decode_single_response(&resp.body, self.utc_offset())?;
+
+        Ok(token)
+    }
+
+    /// Completes a Masterpass payment without 3D Secure
+    #[cfg(feature = "payment")]
+    pub async fn complete_masterpass_payment(
+        &self,
+        params: MasterpassPaymentCompleteRequest,
+    ) -> Result<Payment, CraftgateError> {
+        let request = self
+            .request(
+                Method::POST,
+                "/payment/v1/masterpass-payments/complete",
+                Operation::Mutation,
+            )
+            .json(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
+
+        let payment: Payment = decode_single_response(&resp.body, self.utc_offset())?;
 
-        let payment: Payment = extract_single_response(resp).await?;
         Ok(payment)
     }
 
-    pub async fn expire_common_page_token(&self, token: String) -> Result<(), CraftgateError> {
-        let _resp = self
-            .client
-            .delete(
-                self.base_url
-                    .join(&format!("/payment/v1/checkout-payments/{}", token))
-                    .expect("valid url"),
+    /// Starts 3D Secure for a Masterpass payment. After the buyer returns to the callback URL the
+    /// payment is completed with [`complete_3ds_payment`](Self::complete_3ds_payment).
+    #[cfg(feature = "payment")]
+    pub async fn init_3ds_masterpass_payment(
+        &self,
+        params: MasterpassPaymentThreeDSInitRequest,
+    ) -> Result<MasterpassPaymentThreeDSInitResponse, CraftgateError> {
+        let request = self
+            .request(
+                Method::POST,
+                "/payment/v1/masterpass-payments/3ds-init",
+                Operation::Mutation,
             )
-            .send()
-            .await?
-            .error_for_status()?;
+            .json(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
 
-        Ok(())
+        let resp: MasterpassPaymentThreeDSInitResponse =
+            decode_single_response(&resp.body, self.utc_offset())?;
+
+        Ok(resp)
     }
 
-    pub async fn retrieve_payment(&self, id: String) -> Result<Payment, CraftgateError> {
-        let resp = self
-            .client
-            .get(
-                self.base_url
-                    .join(&format!("/payment/v1/card-payments/{}", id))
-                    .expect("valid url"),
+    #[cfg(feature = "payment")]
+    pub async fn retrieve_masterpass_loyalty(
+        &self,
+        params: MasterpassRetrieveLoyaltiesRequest,
+    ) -> Result<RetrieveLoyaltiesResponse, CraftgateError> {
+        let request = self
+            .request(
+                Method::POST,
+                "/payment/v1/masterpass-payments/loyalties/retrieve",
+                Operation::Safe,
             )
-            .send()
-            .await?;
+            .json(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
+
+        let loyalties: RetrieveLoyaltiesResponse =
+            decode_single_response(&resp.body, self.utc_offset())?;
+
+        Ok(loyalties)
+    }
+
+    /// Initiates a Garanti Pay payment. The buyer is sent to Garanti Pay with the page in the
+    /// response and returns to the callback URL with the result.
+    #[cfg(feature = "payment")]
+    pub async fn init_garanti_pay_payment(
+        &self,
+        params: InitGarantiPayPaymentRequest,
+    ) -> Result<InitGarantiPayPaymentResponse, CraftgateError> {
+        let request = self
+            .request(
+                Method::POST,
+                "/payment/v1/garanti-pay-payments",
+                Operation::Mutation,
+            )
+            .json(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
+
+        let resp: InitGarantiPayPaymentResponse =
+            decode_single_response(&resp.body, self.utc_offset())?;
+
+        Ok(resp)
+    }
+
+    /// Initiates a BKM Express payment. Once the buyer is done on the BKM Express page the
+    /// payment is completed with
+    /// [`complete_bkm_express_payment`](Self::complete_bkm_express_payment).
+    #[cfg(feature = "payment")]
+    pub async fn init_bkm_express_payment(
+        &self,
+        params: InitBkmExpressRequest,
+    ) -> Result<InitBkmExpressResponse, CraftgateError> {
+        let request = self
+            .request(
+                Method::POST,
+                "/payment/v1/bkm-express/init",
+                Operation::Mutation,
+            )
+            .json(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
+
+        let resp: InitBkmExpressResponse = decode_single_response(&resp.body, self.utc_offset())?;
+
+        Ok(resp)
+    }
+
+    /// Completes a BKM Express payment. After an ambiguous failure the payment of the ticket is
+    /// returned if it succeeded. The completion is never sent again, as no status tells a
+    /// completion that was not received apart from one still in progress.
+    #[cfg(feature = "payment")]
+    pub async fn complete_bkm_express_payment(
+        &self,
+        params: CompleteBkmExpressRequest,
+        key: &IdempotencyKey,
+    ) -> Result<Payment, CraftgateError> {
+        let send = || {
+            let params = &params;
+            async move {
+                let request = self
+                    .request(
+                        Method::POST,
+                        "/payment/v1/bkm-express/complete",
+                        Operation::Mutation,
+                    )
+                    .json(params, self.utc_offset())?;
+                let resp = self.send(request).await?;
+
+                decode_single_response(&resp.body, self.utc_offset())
+            }
+        };
+
+        self.execute_idempotent(key, send, |err| async {
+            let payment = self.retrieve_bkm_express_payment(&params.ticket_id).await?;
+            match payment.payment_status {
+                PaymentStatus::Success => Ok(Some(payment)),
+                _ => Err(err),
+            }
+        })
+        .await
+    }
+
+    #[cfg(feature = "payment")]
+    pub async fn retrieve_bkm_express_payment(
+        &self,
+        ticket_id: &BkmExpressTicketId,
+    ) -> Result<Payment, CraftgateError> {
+        let request = self.request(
+            Method::GET,
+            &format!("/payment/v1/bkm-express/payments/{}", ticket_id),
+            Operation::Safe,
+        );
+        let resp = self.send(request).await?;
 
-        let payment: Payment = extract_single_response(resp).await?;
+        let payment: Payment = decode_single_response(&resp.body, self.utc_offset())?;
 
         Ok(payment)
     }
+
+    /// Retrieves the merchant session that validates the merchant when the Apple Pay payment
+    /// sheet opens. The token Apple Pay returns is paid with
+    /// [`create_payment`](Self::create_payment), see
+    /// [`WalletToken`](crate::request::payment::WalletToken).
+    #[cfg(feature = "payment")]
+    pub async fn retrieve_apple_pay_merchant_session(
+        &self,
+        params: ApplePayMerchantSessionRequest,
+    ) -> Result<ApplePayMerchantSession, CraftgateError> {
+        let request = self
+            .request(
+                Method::POST,
+                "/payment/v1/apple-pay/merchant-sessions",
+                Operation::Safe,
+            )
+            .json(&params, self.utc_offset())?;
+        let resp = self.send(request).await?;
+
+        let session: ApplePayMerchantSession =
+            decode_single_response(&resp.body, self.utc_offset())?;
+
+        Ok(session)
+    }
+
+    /// Looks up the payment created with the given `conversation_id` and/or `external_id`, ignoring
+    /// failed payments and, if `created_since` is set, payments created before it. Fails with
+    /// [`CraftgateError::AmbiguousPayment`] when more than one payment matches.
+    #[cfg(feature = "payment")]
+    pub async fn find_payment(
+        &self,
+        conversation_id: Option<&str>,
+        external_id: Option<&str>,
+        created_since: Option<OffsetDateTime>,
+    ) -> Result<Option<Payment>, CraftgateError> {
+        const PAGE_SIZE: u64 = 100;
+
+        let mut found = Vec::new();
+        let mut seen = 0;
+        for page in 0.. {
+            let params = SearchPaymentsRequest::builder()
+                .conversation_id(conversation_id.map(ToOwned::to_owned))
+                .external_id(external_id.map(ToOwned::to_owned))
+                .min_created_date(created_since)
+                .page(page)
+                .size(PAGE_SIZE)
+                .build();
+            let payments = self.search_payments(params).await?;
+
+            let last = (payments.items.len() as u64) < PAGE_SIZE
+                || seen + payments.items.len() >= payments.total_size;
+            seen += payments.items.len();
+            found.extend(
+                payments
+                    .items
+                    .into_iter()
+                    .filter(|payment| !matches!(payment.payment_status, PaymentStatus::Failure)),
+            );
+
+            if last {
+                break;
+            }
+        }
+
+        if found.len() > 1 {
+            return Err(CraftgateError::AmbiguousPayment(
+                found.into_iter().map(|payment| payment.id).collect(),
+            ));
+        }
+
+        Ok(found.pop())
+    }
+
+    /// Sends a payment request that must not be processed twice. When an attempt fails ambiguously
+    /// (see [`CraftgateError::is_ambiguous`]) `lookup` is called with the error to find the result
+    /// of the earlier attempts. The request is only sent again if it returns `None`, and `lookup`
+    /// returns an error, e.g. the one it was called with, when the outcome cannot be told.
+    ///
+    /// Craftgate does not deduplicate requests, so `lookup` must find any result an earlier attempt
+    /// may have produced. `key` is not sent to Craftgate, it identifies the request in traces.
+    #[cfg(feature = "payment")]
+    pub async fn execute_idempotent<R, S, SF, L, LF>(
+        &self,
+        #[cfg_attr(not(feature = "tracing"), allow(unused_variables))] key: &IdempotencyKey,
+        mut send: S,
+        mut lookup: L,
+    ) -> Result<R, CraftgateError>
+    where
+        S: FnMut() -> SF,
+        SF: Future<Output = Result<R, CraftgateError>>,
+        L: FnMut(CraftgateError) -> LF,
+        LF: Future<Output = Result<Option<R>, CraftgateError>>,
+    {
+        let attempts = async {
+            let mut attempt = 1;
+            loop {
+                let err = match send().await {
+                    Ok(result) => return Ok(result),
+                    Err(err) if !err.is_ambiguous() || attempt == MAX_IDEMPOTENT_ATTEMPTS => {
+                        return Err(err)
+                    }
+                    Err(err) => err,
+                };
+
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    attempt,
+                    error = %err,
+                    "payment request failed ambiguously, reconciling"
+                );
+
+                if let Some(result) = lookup(err).await? {
+                    return Ok(result);
+                }
+
+                attempt += 1;
+            }
+        };
+
+        #[cfg(feature = "tracing")]
+        let attempts = tracing::Instrument::instrument(
+            attempts,
+            tracing::info_span!("idempotent_request", idempotency_key = %key),
+        );
+        attempts.await
+    }
 }
 
-#[allow(unused)]
-async fn extract_single_response<T: for<'a> Deserialize<'a>>(
-    resp: Response,
+#[cfg(any(
+    feature = "onboarding",
+    feature = "payment",
+    feature = "settlement",
+    feature = "bank-account-tracking"
+))]
+pub(crate) fn decode_single_response<T: for<'a> Deserialize<'a>>(
+    body: &[u8],
+    offset: UtcOffset,
 ) -> Result<T, CraftgateError> {
-    // let text = resp.text().await?;
-    // dbg!(&text);
-    let resp: ApiResponse<T> = resp.json().await?;
-    // let resp: ApiResponse<T> = serde_json::from_str(&text).unwrap();
+    let resp: ApiResponse<T> = date::with_offset(offset, || serde_json::from_slice(body))?;
+
     match resp.response {
         ApiResponseVariant::Error(e) => Err(CraftgateError::from(e)),
         ApiResponseVariant::Success(succ) => match succ {
@@ -307,11 +1486,17 @@ async fn extract_single_response<T: for<'a> Deserialize<'a>>(
     }
 }
 
-#[allow(unused)]
-async fn extract_paginated_response<T: for<'a> Deserialize<'a>>(
-    resp: Response,
+#[cfg(any(
+    feature = "onboarding",
+    feature = "payment",
+    feature = "settlement",
+    feature = "bank-account-tracking"
+))]
+pub(crate) fn decode_paginated_response<T: for<'a> Deserialize<'a>>(
+    body: &[u8],
+    offset: UtcOffset,
 ) -> Result<PaginatedResponse<T>, CraftgateError> {
-    let resp: ApiResponse<T> = resp.json().await?;
+    let resp: ApiResponse<T> = date::with_offset(offset, || serde_json::from_slice(body))?;
 
     match resp.response {
         ApiResponseVariant::Error(e) => Err(CraftgateError::from(e)),
@@ -323,3 +1508,35 @@ async fn extract_paginated_response<T: for<'a> Deserialize<'a>>(
         },
     }
 }
+
+/// Decodes the response of a request that returns no data on success
+#[cfg(any(feature = "payment", feature = "settlement"))]
+pub(crate) fn decode_empty_response(resp: &HttpResponse) -> Result<(), CraftgateError> {
+    if resp.status.is_success() {
+        return Ok(());
+    }
+
+    Err(decode_error_response(resp))
+}
+
+/// Decodes the response of a request that returns a file, e.g. a CSV report, instead of JSON
+#[cfg(feature = "reporting")]
+pub(crate) fn decode_file_response(resp: HttpResponse) -> Result<Bytes, CraftgateError> {
+    if resp.status.is_success() {
+        return Ok(resp.body);
+    }
+
+    Err(decode_error_response(&resp))
+}
+
+/// Decodes the error of a request that failed with `resp`, which may not have a JSON body
+#[cfg(any(feature = "payment", feature = "settlement", feature = "reporting"))]
+fn decode_error_response(resp: &HttpResponse) -> CraftgateError {
+    match serde_json::from_slice::<ApiResponse<serde_json::Value>>(&resp.body) {
+        Ok(ApiResponse {
+            response: ApiResponseVariant::Error(e),
+            ..
+        }) => CraftgateError::from(e),
+        _ => CraftgateError::HttpStatus(resp.status),
+    }
+}
@@ -0,0 +1,143 @@
+//! Typed identifiers of Craftgate resources, so an id of one kind can't be passed where another is
+//! expected.
+
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+macro_rules! numeric_id {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[serde(transparent)]
+        pub struct $name(pub u64);
+
+        impl From<u64> for $name {
+            fn from(value: u64) -> Self {
+                Self(value)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseIntError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                s.parse().map(Self)
+            }
+        }
+    };
+}
+
+macro_rules! string_id {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+        #[serde(transparent)]
+        pub struct $name(pub String);
+
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(value: String) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                Self(value.to_owned())
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+    };
+}
+
+numeric_id!(
+    /// ID of a buyer or sub-merchant member
+    MemberId
+);
+numeric_id!(
+    /// ID of a payment
+    PaymentId
+);
+numeric_id!(
+    /// ID of a payment split over several cards
+    MultiPaymentId
+);
+numeric_id!(
+    /// ID of a single transaction (payment item) of a payment
+    PaymentTransactionId
+);
+numeric_id!(
+    /// ID of a refund of a payment transaction
+    PaymentTransactionRefundId
+);
+numeric_id!(
+    /// ID of a money transfer to the merchant or a sub-merchant
+    PayoutId
+);
+numeric_id!(
+    /// ID of the wallet of a member
+    WalletId
+);
+numeric_id!(
+    /// ID of a single movement of a wallet balance
+    WalletTransactionId
+);
+numeric_id!(
+    /// ID of an account cross-border settlements are paid out to
+    PayoutAccountId
+);
+numeric_id!(
+    /// ID of a transfer on a tracked bank account
+    BankAccountTrackingRecordId
+);
+string_id!(
+    /// Token of a checkout form, used to inquire or expire it
+    CheckoutToken
+);
+string_id!(
+    /// Ticket of a BKM Express payment, used to complete or retrieve it
+    BkmExpressTicketId
+);
+string_id!(
+    /// Key that represents the holder of stored cards
+    CardUserKey
+);
+string_id!(
+    /// Token that represents a stored card
+    CardToken
+);
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{CheckoutToken, MemberId};
+
+    #[test]
+    fn ids_serialize_as_their_inner_value() {
+        assert_eq!(serde_json::to_value(MemberId(42)).unwrap(), json!(42));
+        assert_eq!(
+            serde_json::from_value::<CheckoutToken>(json!("tok-1")).unwrap(),
+            CheckoutToken::from("tok-1")
+        );
+    }
+}
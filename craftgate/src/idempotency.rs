@@ -0,0 +1,97 @@
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::signature::random_string;
+
+/// Key chosen by the caller for a single logical payment request. The key is never sent to
+/// Craftgate, which processes a resent request again whatever its key. It ties the attempts of
+/// the request together in logs and traces. Use a new key for every payment, e.g. the id of the
+/// order being paid together with the attempt number shown to the buyer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IdempotencyKey(String);
+
+impl IdempotencyKey {
+    /// Fails unless `key` is 1 to 64 ASCII letters, digits, `-`, `_`, `.` or `:`.
+    pub fn new(key: impl Into<String>) -> Result<Self, InvalidIdempotencyKey> {
+        let key = key.into();
+        let valid = (1..=64).contains(&key.len())
+            && key
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b':'));
+
+        if !valid {
+            return Err(InvalidIdempotencyKey(key));
+        }
+
+        Ok(Self(key))
+    }
+
+    pub fn random() -> Self {
+        Self(random_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid idempotency key {0:?}")]
+pub struct InvalidIdempotencyKey(pub String);
+
+impl fmt::Display for IdempotencyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api_error::{ErrorCode, ErrorGroup};
+    use crate::client::CraftgateError;
+
+    use super::IdempotencyKey;
+
+    #[test]
+    fn random_keys_differ() {
+        assert_ne!(IdempotencyKey::random(), IdempotencyKey::random());
+    }
+
+    #[test]
+    fn keys_must_be_printable_ascii() {
+        assert_eq!(
+            IdempotencyKey::new("order-1042:attempt-2")
+                .unwrap()
+                .as_str(),
+            "order-1042:attempt-2"
+        );
+        assert!(IdempotencyKey::new("").is_err());
+        assert!(IdempotencyKey::new("order 1042").is_err());
+        assert!(IdempotencyKey::new("sipariş-1042").is_err());
+        assert!(IdempotencyKey::new("a".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn bank_communication_errors_are_ambiguous() {
+        let err = CraftgateError::PaymentError {
+            code: ErrorCode(10000),
+            description: "Request timed out".to_owned(),
+            group: ErrorGroup::RequestTimeout,
+        };
+        assert!(err.is_ambiguous());
+
+        let err = CraftgateError::PaymentError {
+            code: ErrorCode(10051),
+            description: "Insufficient card limit, insufficient balance".to_owned(),
+            group: ErrorGroup::NotSufficientFunds,
+        };
+        assert!(!err.is_ambiguous());
+
+        let err = CraftgateError::ValidationError {
+            code: ErrorCode(4152),
+            description: "Invalid card expiry year".to_owned(),
+        };
+        assert!(!err.is_ambiguous());
+    }
+}
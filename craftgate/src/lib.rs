@@ -1,6 +1,24 @@
+// The sample payloads in the tests are deeper than the default limit of `serde_json::json!`.
+#![cfg_attr(test, recursion_limit = "256")]
+
 pub mod api_error;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "payment")]
+pub mod checkout;
 pub mod client;
+pub mod id;
+pub mod idempotency;
+#[cfg(feature = "reqwest")]
 pub mod middleware;
+#[cfg(all(feature = "payment", feature = "settlement"))]
+pub mod reconciliation;
 pub mod request;
 pub mod response;
+pub mod retry;
+pub mod signature;
+#[cfg(all(any(test, feature = "test-util"), feature = "reqwest"))]
+pub mod testing;
+#[cfg(test)]
 mod tests;
+pub mod transport;
@@ -1,23 +1,18 @@
 use async_trait::async_trait;
-use bytes::{BufMut, BytesMut};
-use data_encoding::BASE64;
-use hmac_sha256::Hash;
-use rand::distributions::Alphanumeric;
-use rand::{Rng, SeedableRng};
-use rand_chacha::ChaCha20Rng;
-use reqwest::header::{HeaderName, InvalidHeaderValue};
 use reqwest::{Request, Response};
 use reqwest_middleware::{Middleware, Next};
 use secrecy::{ExposeSecret, SecretString};
 use std::str::FromStr;
 use task_local_extensions::Extensions;
-use thiserror::Error;
 
-const X_API_KEY: HeaderName = HeaderName::from_static("x-api-key");
-const X_RND_KEY: HeaderName = HeaderName::from_static("x-rnd-key");
-const X_AUTH_VERSION: HeaderName = HeaderName::from_static("x-auth-version");
-const X_SIGNATURE: HeaderName = HeaderName::from_static("x-signature");
+use crate::signature::{
+    insert_signature_headers, random_string, signature, SignatureCalculationError,
+};
 
+/// Signs requests sent through a `reqwest_middleware` client of your own. [`CraftgateClient`]
+/// signs its requests itself and does not need it.
+///
+/// [`CraftgateClient`]: crate::client::CraftgateClient
 pub struct CraftgateSignatureMiddleware {
     secret_key: SecretString,
     access_key: SecretString,
@@ -34,16 +29,27 @@ impl CraftgateSignatureMiddleware {
 
 impl From<SignatureCalculationError> for reqwest_middleware::Error {
     fn from(value: SignatureCalculationError) -> Self {
-        reqwest_middleware::Error::Middleware(anyhow::anyhow!(value))
+        reqwest_middleware::Error::middleware(value)
     }
 }
 
-#[derive(Error, Debug)]
-pub enum SignatureCalculationError {
-    #[error("Body is incompatible for safe consumption")]
-    IncompatibleBody,
-    #[error("Invalid header value {0}")]
-    InvalidHeaderValue(#[from] InvalidHeaderValue),
+/// Reads the body of `resp` and returns it together with an equivalent response that can still be
+/// consumed by the caller.
+#[cfg(any(feature = "retry", feature = "test-util", test))]
+pub(crate) async fn buffer(resp: Response) -> Result<(Response, bytes::Bytes), reqwest::Error> {
+    let mut builder = http::Response::builder()
+        .status(resp.status())
+        .version(resp.version());
+    if let Some(headers) = builder.headers_mut() {
+        *headers = resp.headers().clone();
+    }
+
+    let body = resp.bytes().await?;
+    let resp = builder
+        .body(body.clone())
+        .expect("parts of a valid response");
+
+    Ok((Response::from(resp), body))
 }
 
 fn calculate_signature(
@@ -52,30 +58,23 @@ fn calculate_signature(
     secret_key: &str,
     random_string: &str,
 ) -> Result<Request, SignatureCalculationError> {
-    let mut payload = BytesMut::new();
-
-    payload.put_slice(req.url().as_str().as_bytes());
-    payload.put_slice(access_key.as_bytes());
-    payload.put_slice(secret_key.as_bytes());
-
-    payload.put_slice(random_string.as_bytes());
-
-    if let Some(body) = req.body() {
-        let Some(body_bytes) = body.as_bytes() else {
-            return Err(SignatureCalculationError::IncompatibleBody);
-        };
-
-        payload.put_slice(body_bytes);
-    }
-
-    let hash = Hash::hash(payload.as_ref());
-
-    let base64_hash = BASE64.encode(&hash);
-    let headers = req.headers_mut();
-    headers.insert(X_API_KEY, access_key.parse()?);
-    headers.insert(X_RND_KEY, random_string.parse()?);
-    headers.insert(X_AUTH_VERSION, "1".parse()?);
-    headers.insert(X_SIGNATURE, base64_hash.parse()?);
+    let body = match req.body() {
+        Some(body) => match body.as_bytes() {
+            Some(body_bytes) => Some(body_bytes),
+            None => return Err(SignatureCalculationError::IncompatibleBody),
+        },
+        None => None,
+    };
+
+    let base64_hash = signature(
+        req.url().as_str(),
+        access_key,
+        secret_key,
+        random_string,
+        body,
+    );
+
+    insert_signature_headers(req.headers_mut(), access_key, random_string, &base64_hash)?;
 
     Ok(req)
 }
@@ -88,12 +87,7 @@ impl Middleware for CraftgateSignatureMiddleware {
         extensions: &mut Extensions,
         next: Next<'_>,
     ) -> reqwest_middleware::Result<Response> {
-        let rng = ChaCha20Rng::from_entropy();
-        let random_string: String = rng
-            .sample_iter(&Alphanumeric)
-            .take(64)
-            .map(char::from)
-            .collect();
+        let random_string = random_string();
 
         let req = calculate_signature(
             req,
@@ -112,7 +106,8 @@ impl Middleware for CraftgateSignatureMiddleware {
 mod tests {
     use reqwest::{Body, Method, Request};
 
-    use super::{calculate_signature, X_API_KEY, X_AUTH_VERSION, X_RND_KEY, X_SIGNATURE};
+    use super::calculate_signature;
+    use crate::signature::{X_API_KEY, X_AUTH_VERSION, X_RND_KEY, X_SIGNATURE};
 
     #[test]
     fn test_without_body() {
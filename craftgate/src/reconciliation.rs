@@ -0,0 +1,544 @@
+//! Reconciliation of the payments of a period against the payouts Craftgate made for them.
+
+use std::collections::HashMap;
+use std::future::Future;
+
+use rust_decimal::Decimal;
+use time::OffsetDateTime;
+
+use crate::client::{CraftgateClient, CraftgateError};
+use crate::id::{PaymentId, PaymentTransactionId};
+use crate::request::payment::search_payments::SearchPaymentsRequest;
+use crate::request::payment::transaction_refund::{
+    PaymentTransactionRefund, RefundStatus, SearchPaymentTransactionRefundsRequest,
+};
+use crate::request::payment::{Payment, PaymentStatus};
+use crate::request::settlement::payout_completed_transaction::{
+    MerchantType, PayoutCompletedTransaction, SearchPayoutCompletedTransactionsRequest,
+};
+use crate::request::{Currency, PayoutReturnStatus};
+use crate::response::PaginatedResponse;
+use crate::transport::Transport;
+
+/// Page size used to pull the records of a period
+const PAGE_SIZE: u64 = 100;
+
+/// Payout amounts of a payment transaction
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PayoutAmounts {
+    pub merchant_payout_amount: Decimal,
+    pub sub_merchant_member_payout_amount: Decimal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconciliationStatus {
+    /// The payouts add up to the expected amounts
+    Matched,
+    /// The transaction is paid out, but not with the expected amounts
+    Mismatch,
+    /// The blockage of the transaction is resolved, but there is no payout for it
+    MissingPayout,
+    /// A payout of the transaction was returned by the receiving bank
+    Bounced,
+    /// The transaction is not paid out yet, its blockage is not resolved
+    NotDue,
+}
+
+/// Expected and actual payouts of a payment transaction
+#[derive(Debug, Clone)]
+pub struct ReconciliationEntry {
+    pub payment_id: PaymentId,
+    pub payment_transaction_id: PaymentTransactionId,
+    /// Currency of the payouts, always `Currency::Try`
+    pub currency: Currency,
+    /// Payout amounts of the transaction, reduced in proportion to the refunds made before it was
+    /// paid out
+    pub expected: PayoutAmounts,
+    /// Sum of the completed payouts of the transaction
+    pub actual: PayoutAmounts,
+    /// Sum of the successful refunds of the transaction
+    pub refunded_amount: Decimal,
+    pub status: ReconciliationStatus,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReconciliationReport {
+    pub start: OffsetDateTime,
+    pub end: OffsetDateTime,
+    pub entries: Vec<ReconciliationEntry>,
+}
+
+impl ReconciliationReport {
+    /// Entries that need attention: mismatched, missing and bounced payouts
+    pub fn discrepancies(&self) -> impl Iterator<Item = &ReconciliationEntry> {
+        self.entries.iter().filter(|entry| {
+            !matches!(
+                entry.status,
+                ReconciliationStatus::Matched | ReconciliationStatus::NotDue
+            )
+        })
+    }
+}
+
+/// Reconciles the successful payments created between `start` and `end` against the payouts
+/// completed for them so far.
+///
+/// Payments, their refunds and the payouts completed since `start` are pulled through `client`,
+/// page by page. Refunds made after a transaction was paid out are ignored, Craftgate collects
+/// them from later payouts.
+pub async fn reconcile<T: Transport>(
+    client: &CraftgateClient<T>,
+    start: OffsetDateTime,
+    end: OffsetDateTime,
+) -> Result<ReconciliationReport, CraftgateError> {
+    let payments = collect_pages(|page| {
+        client.search_payments(
+            SearchPaymentsRequest::builder()
+                .page(page)
+                .size(PAGE_SIZE)
+                .payment_status(Some(PaymentStatus::Success))
+                .min_created_date(Some(start))
+                .max_created_date(Some(end))
+                .build(),
+        )
+    })
+    .await?;
+    let refunds = collect_pages(|page| {
+        client.search_payment_transaction_refunds(
+            SearchPaymentTransactionRefundsRequest::builder()
+                .page(page)
+                .size(PAGE_SIZE)
+                .status(Some(RefundStatus::Success))
+                .min_created_date(Some(start))
+                .build(),
+        )
+    })
+    .await?;
+    let payouts = collect_pages(|page| {
+        client.search_payout_completed_transactions(
+            SearchPayoutCompletedTransactionsRequest::builder()
+                .page(page)
+                .size(PAGE_SIZE)
+                .start_date(Some(start))
+                .build(),
+        )
+    })
+    .await?;
+
+    Ok(build_report(
+        start,
+        end,
+        OffsetDateTime::now_utc(),
+        &payments,
+        &refunds,
+        &payouts,
+    ))
+}
+
+async fn collect_pages<T, F, Fut>(mut search: F) -> Result<Vec<T>, CraftgateError>
+where
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = Result<PaginatedResponse<T>, CraftgateError>>,
+{
+    let mut items = Vec::new();
+    let mut page = 0;
+    loop {
+        let resp = search(page).await?;
+        let last = resp.items.len() < PAGE_SIZE as usize
+            || items.len() + resp.items.len() >= resp.total_size;
+        items.extend(resp.items);
+
+        if last {
+            return Ok(items);
+        }
+        page += 1;
+    }
+}
+
+/// Payouts made to one receiver of a payment transaction
+#[derive(Default)]
+struct ReceiverPayouts {
+    /// Sum of the payouts that reached the receiver
+    amount: Decimal,
+    /// Latest payout returned by the bank
+    returned_at: Option<OffsetDateTime>,
+    /// Latest payout sent again after a return
+    resent_at: Option<OffsetDateTime>,
+}
+
+impl ReceiverPayouts {
+    /// A returned payout is resolved once a later payout completes
+    fn is_bounced(&self) -> bool {
+        match (self.returned_at, self.resent_at) {
+            (Some(returned_at), Some(resent_at)) => resent_at < returned_at,
+            (returned_at, _) => returned_at.is_some(),
+        }
+    }
+}
+
+/// Payouts made for a payment transaction in one currency
+#[derive(Default)]
+struct PaidOut {
+    merchant: ReceiverPayouts,
+    sub_merchant_member: ReceiverPayouts,
+}
+
+impl PaidOut {
+    fn amounts(&self) -> PayoutAmounts {
+        PayoutAmounts {
+            merchant_payout_amount: self.merchant.amount,
+            sub_merchant_member_payout_amount: self.sub_merchant_member.amount,
+        }
+    }
+
+    fn is_bounced(&self) -> bool {
+        self.merchant.is_bounced() || self.sub_merchant_member.is_bounced()
+    }
+}
+
+/// Matches the transactions of `payments` with their refunds and payouts. Transactions whose
+/// blockage resolves after `now` are not expected to be paid out yet.
+fn build_report(
+    start: OffsetDateTime,
+    end: OffsetDateTime,
+    now: OffsetDateTime,
+    payments: &[Payment],
+    refunds: &[PaymentTransactionRefund],
+    payouts: &[PayoutCompletedTransaction],
+) -> ReconciliationReport {
+    let mut refunded: HashMap<PaymentTransactionId, Decimal> = HashMap::new();
+    for refund in refunds {
+        if refund.status == RefundStatus::Success && !refund.is_after_settlement {
+            *refunded.entry(refund.payment_transaction_id).or_default() += refund.refund_price;
+        }
+    }
+
+    let mut paid_out: HashMap<(PaymentTransactionId, Currency), PaidOut> = HashMap::new();
+    for payout in payouts {
+        let Some(transaction_id) = payout.payment_transaction_id else {
+            continue;
+        };
+        let paid_out = paid_out
+            .entry((transaction_id, payout.currency.clone()))
+            .or_default();
+        let receiver = match payout.merchant_type {
+            MerchantType::SubMerchantMember => &mut paid_out.sub_merchant_member,
+            _ => &mut paid_out.merchant,
+        };
+        match payout.payout_return_status {
+            // Returned by the bank, the re-sent payout is only counted once it completes.
+            Some(
+                PayoutReturnStatus::Bounced
+                | PayoutReturnStatus::Updated
+                | PayoutReturnStatus::PayoutStarted,
+            ) => {
+                receiver.returned_at = receiver.returned_at.max(Some(payout.payout_date));
+                continue;
+            }
+            Some(PayoutReturnStatus::PayoutCompleted) => {
+                receiver.resent_at = receiver.resent_at.max(Some(payout.payout_date));
+            }
+            _ => {}
+        }
+        receiver.amount += payout.payout_amount;
+    }
+
+    let mut entries = Vec::new();
+    for payment in payments {
+        for transaction in &payment.payment_transactions {
+            let refunded_amount = refunded.get(&transaction.id).copied().unwrap_or_default();
+            // Payouts are made in the currency of the distribution, not of the payment
+            let currency = &transaction.payout.currency;
+            let expected = expected_payout(
+                PayoutAmounts {
+                    merchant_payout_amount: transaction.payout.merchant_payout_amount,
+                    sub_merchant_member_payout_amount: transaction
+                        .payout
+                        .sub_merchant_member_payout_amount,
+                },
+                transaction.paid_price,
+                refunded_amount,
+                currency.scale(),
+            );
+            let payout = paid_out.get(&(transaction.id, currency.clone()));
+            let actual = payout.map(PaidOut::amounts).unwrap_or_default();
+            // `is_none_or` needs Rust 1.82
+            #[allow(clippy::unnecessary_map_or)]
+            let not_due = transaction
+                .blockage_resolved_date
+                .map_or(true, |date| date > now);
+
+            let status = match payout {
+                Some(payout) if payout.is_bounced() => ReconciliationStatus::Bounced,
+                Some(_) if actual == expected => ReconciliationStatus::Matched,
+                Some(_) => ReconciliationStatus::Mismatch,
+                None if expected == PayoutAmounts::default() => ReconciliationStatus::Matched,
+                None if not_due => ReconciliationStatus::NotDue,
+                None => ReconciliationStatus::MissingPayout,
+            };
+
+            entries.push(ReconciliationEntry {
+                payment_id: payment.id,
+                payment_transaction_id: transaction.id,
+                currency: currency.clone(),
+                expected,
+                actual,
+                refunded_amount,
+                status,
+            });
+        }
+    }
+
+    ReconciliationReport {
+        start,
+        end,
+        entries,
+    }
+}
+
+/// Scales `amounts` down to the part of `paid_price` that is not refunded
+fn expected_payout(
+    amounts: PayoutAmounts,
+    paid_price: Decimal,
+    refunded: Decimal,
+    scale: u32,
+) -> PayoutAmounts {
+    if refunded.is_zero() || paid_price.is_zero() {
+        return amounts;
+    }
+    let remaining = (paid_price - refunded).max(Decimal::ZERO) / paid_price;
+
+    PayoutAmounts {
+        merchant_payout_amount: (amounts.merchant_payout_amount * remaining).round_dp(scale),
+        sub_merchant_member_payout_amount: (amounts.sub_merchant_member_payout_amount * remaining)
+            .round_dp(scale),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+    use time::macros::datetime;
+
+    use super::{build_report, PayoutAmounts, ReconciliationStatus};
+    use crate::request::payment::transaction_refund::PaymentTransactionRefund;
+    use crate::request::payment::Payment;
+    use crate::request::settlement::payout_completed_transaction::PayoutCompletedTransaction;
+    use crate::request::Currency;
+
+    fn transaction(id: u64, paid_price: u64, blockage_resolved_date: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "price": paid_price,
+            "paidPrice": paid_price,
+            "walletPrice": 0,
+            "merchantCommissionRate": 0,
+            "merchantCommissionRateAmount": 0,
+            "merchantPayoutAmount": 10,
+            "subMerchantMemberId": 116213,
+            "subMerchantMemberPrice": paid_price - 10,
+            "subMerchantMemberPayoutRate": 100,
+            "subMerchantMemberPayoutAmount": paid_price - 10,
+            "transactionStatus": "APPROVED_BY_MERCHANT",
+            "blockageResolvedDate": blockage_resolved_date,
+            "payout": {
+                "paidPrice": paid_price,
+                "parity": 1,
+                "currency": "TRY",
+                "merchantPayoutAmount": 10,
+                "subMerchantMemberPayoutAmount": paid_price - 10
+            }
+        })
+    }
+
+    fn payout(
+        transaction_id: u64,
+        merchant_type: &str,
+        amount: u64,
+        return_status: &str,
+    ) -> PayoutCompletedTransaction {
+        payout_on(
+            transaction_id,
+            merchant_type,
+            amount,
+            return_status,
+            "2023-11-10T09:00:00",
+        )
+    }
+
+    fn payout_on(
+        transaction_id: u64,
+        merchant_type: &str,
+        amount: u64,
+        return_status: &str,
+        payout_date: &str,
+    ) -> PayoutCompletedTransaction {
+        serde_json::from_value(serde_json::json!({
+            "payoutId": transaction_id * 10,
+            "paymentId": 1,
+            "paymentTransactionId": transaction_id,
+            "payoutAmount": amount,
+            "payoutDate": payout_date,
+            "currency": "TRY",
+            "merchantType": merchant_type,
+            "payoutReturnStatus": return_status
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn flags_payout_discrepancies() {
+        let mut payment = serde_json::from_str::<serde_json::Value>(include_str!(
+            "../fixtures/payment/retrieve_card_payment.json"
+        ))
+        .unwrap()[0]["response"]["body"]["data"]
+            .take();
+        payment["paymentTransactions"] = serde_json::json!([
+            transaction(1, 100, "2023-11-09T00:00:00"),
+            transaction(2, 100, "2023-11-09T00:00:00"),
+            transaction(3, 100, "2023-11-09T00:00:00"),
+            transaction(4, 100, "2023-11-20T00:00:00"),
+            transaction(5, 100, "2023-11-09T00:00:00"),
+            transaction(6, 100, "2023-11-09T00:00:00"),
+        ]);
+        let payment: Payment = serde_json::from_value(payment).unwrap();
+        let refund: PaymentTransactionRefund = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "createdDate": "2023-11-08T12:00:00",
+            "status": "SUCCESS",
+            "isAfterSettlement": false,
+            "refundPrice": 50,
+            "conversationId": null,
+            "paymentId": 1,
+            "paymentTransactionId": 6,
+            "currency": "TRY"
+        }))
+        .unwrap();
+        let payouts = [
+            payout(1, "MERCHANT", 10, "NOT_BOUNCED"),
+            payout(1, "SUB_MERCHANT_MEMBER", 90, "NOT_BOUNCED"),
+            payout(2, "MERCHANT", 10, "NOT_BOUNCED"),
+            payout(2, "SUB_MERCHANT_MEMBER", 80, "NOT_BOUNCED"),
+            payout(5, "SUB_MERCHANT_MEMBER", 90, "BOUNCED"),
+            payout(6, "MERCHANT", 5, "NOT_BOUNCED"),
+            payout(6, "SUB_MERCHANT_MEMBER", 45, "NOT_BOUNCED"),
+        ];
+
+        let report = build_report(
+            datetime!(2023-11-01 00:00 +3),
+            datetime!(2023-11-08 00:00 +3),
+            datetime!(2023-11-15 00:00 +3),
+            &[payment],
+            &[refund],
+            &payouts,
+        );
+
+        let statuses: Vec<_> = report.entries.iter().map(|entry| entry.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                ReconciliationStatus::Matched,
+                ReconciliationStatus::Mismatch,
+                ReconciliationStatus::MissingPayout,
+                ReconciliationStatus::NotDue,
+                ReconciliationStatus::Bounced,
+                ReconciliationStatus::Matched,
+            ]
+        );
+        assert_eq!(
+            report.entries[5].expected,
+            PayoutAmounts {
+                merchant_payout_amount: Decimal::new(5, 0),
+                sub_merchant_member_payout_amount: Decimal::new(45, 0),
+            }
+        );
+        assert_eq!(report.discrepancies().count(), 3);
+    }
+
+    #[test]
+    fn resent_payouts_resolve_bounces() {
+        let mut payment = serde_json::from_str::<serde_json::Value>(include_str!(
+            "../fixtures/payment/retrieve_card_payment.json"
+        ))
+        .unwrap()[0]["response"]["body"]["data"]
+            .take();
+        payment["paymentTransactions"] = serde_json::json!([
+            transaction(1, 100, "2023-11-09T00:00:00"),
+            transaction(2, 100, "2023-11-09T00:00:00"),
+            transaction(3, 100, "2023-11-09T00:00:00"),
+        ]);
+        let payment: Payment = serde_json::from_value(payment).unwrap();
+        let payouts = [
+            payout(1, "MERCHANT", 10, "NOT_BOUNCED"),
+            payout(1, "SUB_MERCHANT_MEMBER", 90, "BOUNCED"),
+            payout_on(
+                1,
+                "SUB_MERCHANT_MEMBER",
+                90,
+                "PAYOUT_COMPLETED",
+                "2023-11-13T09:00:00",
+            ),
+            payout_on(2, "MERCHANT", 10, "PAYOUT_COMPLETED", "2023-11-09T09:00:00"),
+            payout(2, "SUB_MERCHANT_MEMBER", 90, "BOUNCED"),
+            payout(3, "SUB_MERCHANT_MEMBER", 90, "BOUNCED"),
+            payout_on(3, "MERCHANT", 10, "PAYOUT_COMPLETED", "2023-11-13T09:00:00"),
+        ];
+
+        let report = build_report(
+            datetime!(2023-11-01 00:00 +3),
+            datetime!(2023-11-08 00:00 +3),
+            datetime!(2023-11-15 00:00 +3),
+            &[payment],
+            &[],
+            &payouts,
+        );
+
+        let statuses: Vec<_> = report.entries.iter().map(|entry| entry.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                ReconciliationStatus::Matched,
+                ReconciliationStatus::Bounced,
+                ReconciliationStatus::Bounced,
+            ]
+        );
+    }
+
+    #[test]
+    fn compares_foreign_currency_payments_with_try_payouts() {
+        let mut payment = serde_json::from_str::<serde_json::Value>(include_str!(
+            "../fixtures/payment/retrieve_card_payment.json"
+        ))
+        .unwrap()[0]["response"]["body"]["data"]
+            .take();
+        payment["currency"] = "USD".into();
+        let mut transaction = transaction(1, 10, "2023-11-09T00:00:00");
+        transaction["merchantPayoutAmount"] = 1.into();
+        transaction["subMerchantMemberPayoutAmount"] = 9.into();
+        transaction["payout"] = serde_json::json!({
+            "paidPrice": 300,
+            "parity": 30,
+            "currency": "TRY",
+            "merchantPayoutAmount": 30,
+            "subMerchantMemberPayoutAmount": 270
+        });
+        payment["paymentTransactions"] = serde_json::json!([transaction]);
+        let payment: Payment = serde_json::from_value(payment).unwrap();
+        let payouts = [
+            payout(1, "MERCHANT", 30, "NOT_BOUNCED"),
+            payout(1, "SUB_MERCHANT_MEMBER", 270, "NOT_BOUNCED"),
+        ];
+
+        let report = build_report(
+            datetime!(2023-11-01 00:00 +3),
+            datetime!(2023-11-08 00:00 +3),
+            datetime!(2023-11-15 00:00 +3),
+            &[payment],
+            &[],
+            &payouts,
+        );
+
+        assert_eq!(report.entries[0].status, ReconciliationStatus::Matched);
+        assert_eq!(report.entries[0].currency, Currency::Try);
+    }
+}
@@ -0,0 +1,40 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_enum_str::{Deserialize_enum_str, Serialize_enum_str};
+use time::OffsetDateTime;
+
+use crate::id::{BankAccountTrackingRecordId, MemberId};
+use crate::request::Currency;
+
+pub mod search_records;
+
+/// Direction of a transfer on a tracked bank account
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RecordType {
+    /// Money sent from the account
+    Send,
+    /// Money received by the account, e.g. an incoming EFT or FAST transfer
+    Receive,
+    #[serde(other)]
+    Unhandled(String),
+}
+
+/// A transfer on a bank account of the merchant that Craftgate tracks
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BankAccountTrackingRecord {
+    pub id: BankAccountTrackingRecordId,
+    /// Reference of the transfer at the bank
+    pub key: Option<String>,
+    pub sender_name: Option<String>,
+    pub sender_iban: Option<String>,
+    pub description: Option<String>,
+    pub currency: Currency,
+    pub amount: Decimal,
+    #[serde(with = "crate::request::date::craftgate")]
+    pub record_date: OffsetDateTime,
+    pub record_type: RecordType,
+    /// Member the transfer is matched to, if Craftgate could match it
+    pub member_id: Option<MemberId>,
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use typed_builder::TypedBuilder;
+
+use crate::request::bank_account_tracking::RecordType;
+use crate::request::Currency;
+
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default))]
+pub struct SearchBankAccountTrackingRecordsRequest {
+    #[builder(default = 0)]
+    pub page: u64,
+    #[builder(default = 25)]
+    pub size: u64,
+    pub sender_name: Option<String>,
+    pub sender_iban: Option<String>,
+    pub description: Option<String>,
+    pub currency: Option<Currency>,
+    pub record_type: Option<RecordType>,
+    #[serde(with = "crate::request::date::craftgate::option")]
+    #[serde(default)]
+    pub min_record_date: Option<OffsetDateTime>,
+    #[serde(with = "crate::request::date::craftgate::option")]
+    #[serde(default)]
+    pub max_record_date: Option<OffsetDateTime>,
+}
+
+impl Default for SearchBankAccountTrackingRecordsRequest {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
@@ -0,0 +1,161 @@
+//! Craftgate sends and expects timestamps in its local time, without an offset. They are read as
+//! [`OffsetDateTime`]s in the offset of the client, [`DEFAULT_OFFSET`] (`+03:00`,
+//! Europe/Istanbul) unless set in its options, and converted to that offset before being sent.
+
+use std::cell::Cell;
+
+use time::format_description::FormatItem;
+use time::macros::{format_description, offset};
+use time::{OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+/// Offset of Craftgate timestamps unless the client is given another one
+pub const DEFAULT_OFFSET: UtcOffset = offset!(+3);
+
+thread_local! {
+    /// Offset of the client encoding or decoding on this thread, see [`with_offset`]
+    static OFFSET: Cell<UtcOffset> = const { Cell::new(DEFAULT_OFFSET) };
+}
+
+/// Some endpoints send fractional seconds
+const PARSE_FORMAT: &[FormatItem<'static>] = format_description!(
+    "[year]-[month]-[day]T[hour repr:24]:[minute]:[second][optional [.[subsecond]]]"
+);
+const FORMAT: &[FormatItem<'static>] =
+    format_description!("[year]-[month]-[day]T[hour repr:24]:[minute]:[second]");
+
+/// Runs `f`, which must not yield to other tasks, with timestamps read and written in `offset`.
+/// Serde functions can not be handed the offset of the client, so it is passed on the side.
+pub(crate) fn with_offset<R>(offset: UtcOffset, f: impl FnOnce() -> R) -> R {
+    struct Restore(UtcOffset);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            OFFSET.with(|current| current.set(self.0));
+        }
+    }
+
+    let _restore = Restore(OFFSET.with(|current| current.replace(offset)));
+
+    f()
+}
+
+pub(crate) fn parse(value: &str) -> Result<OffsetDateTime, time::error::Parse> {
+    Ok(PrimitiveDateTime::parse(value, PARSE_FORMAT)?.assume_offset(OFFSET.with(Cell::get)))
+}
+
+pub(crate) fn format(value: OffsetDateTime) -> Result<String, time::error::Format> {
+    value.to_offset(OFFSET.with(Cell::get)).format(FORMAT)
+}
+
+/// Serde functions for Craftgate timestamps, used with `#[serde(with = "...")]`
+pub(crate) mod craftgate {
+    use serde::{de, ser, Deserialize, Deserializer, Serializer};
+    use time::OffsetDateTime;
+
+    pub fn serialize<S: Serializer>(
+        value: &OffsetDateTime,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let value = super::format(*value).map_err(ser::Error::custom)?;
+
+        serializer.serialize_str(&value)
+    }
+
+    pub fn deserialize<'a, D: Deserializer<'a>>(
+        deserializer: D,
+    ) -> Result<OffsetDateTime, D::Error> {
+        let value = String::deserialize(deserializer)?;
+
+        super::parse(&value).map_err(de::Error::custom)
+    }
+
+    /// Optional timestamps. The value is read as a string first, since responses go through
+    /// untagged enums that hand a `null` over in a form `time`'s own serde support can not read.
+    pub mod option {
+        use serde::{de, Deserialize, Deserializer, Serializer};
+        use time::OffsetDateTime;
+
+        pub fn serialize<S: Serializer>(
+            value: &Option<OffsetDateTime>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match value {
+                Some(value) => super::serialize(value, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'a, D: Deserializer<'a>>(
+            deserializer: D,
+        ) -> Result<Option<OffsetDateTime>, D::Error> {
+            Option::<String>::deserialize(deserializer)?
+                .map(|value| super::super::parse(&value).map_err(de::Error::custom))
+                .transpose()
+        }
+    }
+}
+
+/// Serde functions for calendar days, e.g. the day of a report
+#[cfg(feature = "reporting")]
+pub(crate) mod day {
+    use serde::{de, ser, Deserialize, Deserializer, Serializer};
+    use time::format_description::FormatItem;
+    use time::macros::format_description;
+    use time::Date;
+
+    const FORMAT: &[FormatItem<'static>] = format_description!("[year]-[month]-[day]");
+
+    pub fn serialize<S: Serializer>(value: &Date, serializer: S) -> Result<S::Ok, S::Error> {
+        let value = value.format(FORMAT).map_err(ser::Error::custom)?;
+
+        serializer.serialize_str(&value)
+    }
+
+    pub fn deserialize<'a, D: Deserializer<'a>>(deserializer: D) -> Result<Date, D::Error> {
+        let value = String::deserialize(deserializer)?;
+
+        Date::parse(&value, FORMAT).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::{datetime, offset};
+
+    use super::{format, parse, with_offset};
+
+    #[test]
+    fn timestamps_are_in_craftgate_offset() {
+        assert_eq!(
+            parse("2023-10-24T11:52:37").unwrap(),
+            datetime!(2023-10-24 11:52:37 +3)
+        );
+        assert_eq!(
+            format(datetime!(2023-10-24 08:52:37 UTC)).unwrap(),
+            "2023-10-24T11:52:37"
+        );
+        assert_eq!(
+            format(datetime!(2023-10-24 11:52:37 +3).to_offset(offset!(-5))).unwrap(),
+            "2023-10-24T11:52:37"
+        );
+    }
+
+    #[test]
+    fn offset_is_scoped() {
+        let parsed = with_offset(offset!(+1), || parse("2023-10-24T11:52:37").unwrap());
+
+        assert_eq!(parsed, datetime!(2023-10-24 11:52:37 +1));
+        assert_eq!(
+            parse("2023-10-24T11:52:37").unwrap(),
+            datetime!(2023-10-24 11:52:37 +3)
+        );
+    }
+
+    #[test]
+    fn fractional_seconds_are_read() {
+        assert_eq!(
+            parse("2023-10-24T11:52:37.125").unwrap(),
+            datetime!(2023-10-24 11:52:37.125 +3)
+        );
+    }
+}
@@ -1,21 +1,41 @@
-use serde::{Deserialize, Serialize};
-use time::serde::format_description;
+use serde_enum_str::{Deserialize_enum_str, Serialize_enum_str};
 
+#[cfg(feature = "bank-account-tracking")]
+pub mod bank_account_tracking;
+#[cfg(any(
+    feature = "onboarding",
+    feature = "payment",
+    feature = "settlement",
+    feature = "bank-account-tracking"
+))]
+pub mod date;
+pub mod money;
+#[cfg(feature = "onboarding")]
 pub mod onboarding;
+#[cfg(feature = "payment")]
 pub mod payment;
+#[cfg(feature = "reporting")]
+pub mod reporting;
+#[cfg(feature = "settlement")]
+pub mod settlement;
+pub mod validation;
+#[cfg(feature = "wallet")]
+pub mod wallet;
 
 /// Status that can be used when communicating with the Craftgate API:
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Status {
     /// Active, represents records that available to be used
     Active,
     /// Passive, represents records that not available to be used
     Passive,
+    #[serde(other)]
+    Unhandled(String),
 }
 
 /// Currencies that can be used when communicating with the Craftgate API:
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Default, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Currency {
     /// Turkish Lira
@@ -41,10 +61,40 @@ pub enum Currency {
     Azn,
     /// Kazakhstani Tenge
     Kzt,
+    #[serde(other)]
+    Unhandled(String),
+}
+
+impl Currency {
+    /// ISO 4217 code of the currency
+    pub fn code(&self) -> &str {
+        match self {
+            Currency::Try => "TRY",
+            Currency::Usd => "USD",
+            Currency::Eur => "EUR",
+            Currency::Gbp => "GBP",
+            Currency::Cny => "CNY",
+            Currency::Ars => "ARS",
+            Currency::Brl => "BRL",
+            Currency::Aed => "AED",
+            Currency::Iqd => "IQD",
+            Currency::Azn => "AZN",
+            Currency::Kzt => "KZT",
+            Currency::Unhandled(code) => code,
+        }
+    }
+
+    /// Number of decimal places amounts in the currency can have
+    pub fn scale(&self) -> u32 {
+        match self {
+            Currency::Iqd => 3,
+            _ => 2,
+        }
+    }
 }
 
 /// Types expressing the configuration of the use of card:
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum CardType {
     /// Credit card
@@ -53,10 +103,26 @@ pub enum CardType {
     DebitCard,
     /// Prepaid card
     PrepaidCard,
+    #[serde(other)]
+    Unhandled(String),
 }
 
-format_description!(
-    craftgate,
-    PrimitiveDateTime,
-    "[year]-[month]-[day]T[hour repr:24]:[minute]:[second]"
-);
+/// Whether a money transfer to a member was returned by the receiving bank, and its resending
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PayoutReturnStatus {
+    /// The first value that occurs when money is sent. It means that the money transfer is sent on
+    /// the first try.
+    NotBounced,
+    /// The status that occurs when the money transfer is marked as returned.
+    Bounced,
+    /// The status that occurs when the sub-merchant information is updated together with the Name
+    /// / Surname / Iban information that is the subject of money transfer.
+    Updated,
+    /// The status that occurs when sending money again for a returned transaction.
+    PayoutStarted,
+    /// The status that occurs after the money transfer is made again for a returned transaction.
+    PayoutCompleted,
+    #[serde(other)]
+    Unhandled(String),
+}
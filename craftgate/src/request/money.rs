@@ -0,0 +1,153 @@
+use std::fmt;
+
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+use crate::request::Currency;
+
+/// An amount in a given currency
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Money {
+    pub amount: Decimal,
+    pub currency: Currency,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MoneyError {
+    #[error("{amount} has more than {scale} decimal places allowed for {}", currency.code())]
+    InvalidScale {
+        amount: Decimal,
+        currency: Currency,
+        scale: u32,
+    },
+    #[error("Cannot combine amounts in {} and {}", left.code(), right.code())]
+    CurrencyMismatch { left: Currency, right: Currency },
+    #[error("Amount overflowed")]
+    Overflow,
+}
+
+impl Money {
+    /// Creates the amount after checking it has no more decimal places than the currency allows
+    pub fn new(amount: Decimal, currency: Currency) -> Result<Self, MoneyError> {
+        let money = Self { amount, currency };
+        money.validate_scale()?;
+
+        Ok(money)
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Self {
+            amount: Decimal::ZERO,
+            currency,
+        }
+    }
+
+    /// Checks the amount has no more decimal places than [`Currency::scale`]. Trailing zeros are
+    /// ignored, so `10.500` is a valid TRY amount.
+    pub fn validate_scale(&self) -> Result<(), MoneyError> {
+        let scale = self.currency.scale();
+        if self.amount.normalize().scale() > scale {
+            return Err(MoneyError::InvalidScale {
+                amount: self.amount,
+                currency: self.currency.clone(),
+                scale,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn checked_add(self, other: Money) -> Result<Money, MoneyError> {
+        self.same_currency(&other)?;
+        let amount = self
+            .amount
+            .checked_add(other.amount)
+            .ok_or(MoneyError::Overflow)?;
+
+        Ok(Self { amount, ..self })
+    }
+
+    pub fn checked_sub(self, other: Money) -> Result<Money, MoneyError> {
+        self.same_currency(&other)?;
+        let amount = self
+            .amount
+            .checked_sub(other.amount)
+            .ok_or(MoneyError::Overflow)?;
+
+        Ok(Self { amount, ..self })
+    }
+
+    /// Sums `amounts` in `currency`. An empty iterator sums to zero.
+    pub fn checked_sum(
+        currency: Currency,
+        amounts: impl IntoIterator<Item = Money>,
+    ) -> Result<Money, MoneyError> {
+        amounts
+            .into_iter()
+            .try_fold(Self::zero(currency), Money::checked_add)
+    }
+
+    fn same_currency(&self, other: &Money) -> Result<(), MoneyError> {
+        if self.currency != other.currency {
+            return Err(MoneyError::CurrencyMismatch {
+                left: self.currency.clone(),
+                right: other.currency.clone(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.amount, self.currency.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use super::{Money, MoneyError};
+    use crate::request::Currency;
+
+    #[test]
+    fn scale_depends_on_currency() {
+        assert!(Money::new(Decimal::new(10_50, 2), Currency::Try).is_ok());
+        assert!(Money::new(Decimal::new(10_500, 3), Currency::Try).is_ok());
+        assert!(Money::new(Decimal::new(10_505, 3), Currency::Iqd).is_ok());
+        assert_eq!(
+            Money::new(Decimal::new(10_505, 3), Currency::Usd),
+            Err(MoneyError::InvalidScale {
+                amount: Decimal::new(10_505, 3),
+                currency: Currency::Usd,
+                scale: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn arithmetic_requires_the_same_currency() {
+        let try_amount = Money::new(Decimal::new(10_00, 2), Currency::Try).unwrap();
+        let usd_amount = Money::new(Decimal::new(2_50, 2), Currency::Usd).unwrap();
+
+        assert_eq!(
+            try_amount.clone().checked_add(usd_amount),
+            Err(MoneyError::CurrencyMismatch {
+                left: Currency::Try,
+                right: Currency::Usd,
+            })
+        );
+        assert_eq!(
+            try_amount.clone().checked_sub(try_amount.clone()).unwrap(),
+            Money::zero(Currency::Try)
+        );
+        assert_eq!(
+            Money::new(Decimal::MAX, Currency::Try)
+                .unwrap()
+                .checked_add(try_amount),
+            Err(MoneyError::Overflow)
+        );
+    }
+}
@@ -2,11 +2,14 @@ pub mod create_member;
 pub mod search_members;
 pub mod update_member;
 
+use crate::id::MemberId;
+pub use crate::request::PayoutReturnStatus;
 use crate::request::Status;
 use serde::{Deserialize, Serialize};
-use time::PrimitiveDateTime;
+use serde_enum_str::{Deserialize_enum_str, Serialize_enum_str};
+use time::OffsetDateTime;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum MemberType {
     /// Personal member
@@ -15,27 +18,33 @@ pub enum MemberType {
     PrivateCompany,
     /// Limited or joint stock company member
     LimitedOrJointStockCompany,
+    #[serde(other)]
+    Unhandled(String),
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PayoutDestination {
     /// IBAN
     Iban,
     /// Member Wallet
     Wallet,
+    #[serde(other)]
+    Unhandled(String),
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PayoutMerchantType {
     /// Merchant
     Merchant,
     /// Sub merchant member
     SubMerchantMember,
+    #[serde(other)]
+    Unhandled(String),
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PayoutTransactionType {
     /// Payout for payments
@@ -44,48 +53,30 @@ pub enum PayoutTransactionType {
     Withdraw,
     /// Payout for refund after settlement
     Ras,
+    #[serde(other)]
+    Unhandled(String),
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum PayoutReturnStatus {
-    /// The first value that occurs when money is sent. It means that the money transfer is sent on
-    /// the first try.
-    NotBounced,
-    /// The status that occurs when the money transfer is marked as returned.
-    Bounced,
-    /// The status that occurs when the sub-merchant information is updated together with the Name
-    /// / Surname / Iban information that is the subject of money transfer.
-    Updated,
-    /// The status that occurs when sending money again for a returned transaction.
-    PayoutStarted,
-    /// The status that occurs after the money transfer is made again for a returned transaction.
-    PayoutCompleted,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Default, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum SettlementEarningsDestination {
+    #[default]
     Iban,
     Wallet,
     CrossBorder,
-}
-
-impl Default for SettlementEarningsDestination {
-    fn default() -> Self {
-        Self::Iban
-    }
+    #[serde(other)]
+    Unhandled(String),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Member {
-    pub id: u64,
-    #[serde(with = "crate::request::craftgate")]
-    pub created_date: PrimitiveDateTime,
-    #[serde(with = "crate::request::craftgate::option")]
+    pub id: MemberId,
+    #[serde(with = "crate::request::date::craftgate")]
+    pub created_date: OffsetDateTime,
+    #[serde(with = "crate::request::date::craftgate::option")]
     #[serde(default)]
-    pub updated_date: Option<PrimitiveDateTime>,
+    pub updated_date: Option<OffsetDateTime>,
     pub status: Status,
     /// Set this parameter true if member is a buyer
     pub is_buyer: Option<bool>,
@@ -1,3 +1,4 @@
+use crate::id::MemberId;
 use crate::request::onboarding::MemberType;
 use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
@@ -14,7 +15,7 @@ pub struct SearchMembersRequest {
     pub is_sub_merchant: Option<bool>,
     pub member_type: Option<MemberType>,
     pub member_external_id: Option<String>,
-    pub member_ids: Option<Vec<u64>>,
+    pub member_ids: Option<Vec<MemberId>>,
     pub name: Option<String>,
 }
 
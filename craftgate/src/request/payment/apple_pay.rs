@@ -0,0 +1,32 @@
+//! Apple Pay on the merchant's own pages. Apple Pay JS asks the merchant to validate itself when
+//! the payment sheet opens, which is done with a merchant session Craftgate retrieves from Apple.
+//! The encrypted token Apple Pay returns is then paid with a
+//! [`WalletToken`](crate::request::payment::WalletToken) on the card of a direct payment.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use typed_builder::TypedBuilder;
+
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplePayMerchantSessionRequest {
+    /// Domain the payment sheet is shown on, registered for Apple Pay in the Craftgate panel
+    #[serde(rename = "initiativeContext")]
+    pub domain: String,
+    /// Name of the merchant shown on the payment sheet
+    pub display_name: String,
+    /// `validationURL` of the `onvalidatemerchant` event of Apple Pay JS
+    #[serde(rename = "validationUrl")]
+    pub validation_url: String,
+    #[builder(default = "web".to_owned())]
+    pub initiative: String,
+    /// Apple Pay merchant identifier, the one set up in the Craftgate panel is used if missing
+    #[builder(default)]
+    pub merchant_identifier: Option<String>,
+}
+
+/// Merchant session as Apple returned it, to be passed to `completeMerchantValidation` of Apple
+/// Pay JS unchanged
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+pub struct ApplePayMerchantSession(pub Value);
@@ -0,0 +1,52 @@
+//! Payments with BKM Express. The buyer pays on the BKM Express page opened with the init
+//! response, after which the payment is completed with the ticket and the result BKM Express
+//! reported.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::id::{BkmExpressTicketId, MemberId};
+use crate::request::payment::{PaymentGroup, PaymentItem, PaymentPhase};
+use crate::request::Currency;
+
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default))]
+pub struct InitBkmExpressRequest {
+    #[builder(!default)]
+    pub price: Decimal,
+    #[builder(!default)]
+    pub paid_price: Decimal,
+    pub currency: Currency,
+    pub conversation_id: Option<String>,
+    pub external_id: Option<String>,
+    pub bank_order_id: Option<String>,
+    pub payment_group: PaymentGroup,
+    pub payment_phase: PaymentPhase,
+    pub buyer_member_id: Option<MemberId>,
+    pub enabled_installments: Option<Vec<u64>>,
+    #[builder(!default)]
+    pub items: Vec<PaymentItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InitBkmExpressResponse {
+    /// Ticket of the payment at BKM Express
+    pub id: BkmExpressTicketId,
+    /// Path of the BKM Express page
+    pub path: String,
+    /// Token the BKM Express page is opened with
+    pub token: String,
+}
+
+/// Result BKM Express reported for a ticket
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+pub struct CompleteBkmExpressRequest {
+    /// Whether BKM Express reported the payment as successful
+    pub status: bool,
+    pub message: String,
+    pub ticket_id: BkmExpressTicketId,
+}
@@ -1,8 +1,15 @@
+use std::ops::RangeInclusive;
+
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
 use typed_builder::TypedBuilder;
+use url::Url;
 
+use crate::id::{CardUserKey, CheckoutToken, MemberId};
+use crate::request::money::{Money, MoneyError};
 use crate::request::payment::{PaymentGroup, PaymentItem, PaymentMethod, PaymentPhase};
+use crate::request::validation::{Validate, Violation, ViolationKind};
 use crate::request::Currency;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, TypedBuilder)]
@@ -16,14 +23,14 @@ pub struct CheckoutPaymentInitiationRequest {
     pub price: Decimal,
     #[builder(!default)]
     pub paid_price: Decimal,
-    pub buyer_member_id: Option<String>,
+    pub buyer_member_id: Option<MemberId>,
     pub currency: Currency,
     pub payment_group: PaymentGroup,
     pub payment_phase: PaymentPhase,
     pub payment_channel: Option<String>,
     #[builder(!default)]
     pub callback_url: String,
-    pub card_user_key: Option<String>,
+    pub card_user_key: Option<CardUserKey>,
     pub enabled_installments: Option<Vec<u64>>,
     pub allow_only_credit_card: Option<bool>,
     pub allow_only_stored_cards: Option<bool>,
@@ -38,13 +45,291 @@ pub struct CheckoutPaymentInitiationRequest {
     pub masterpass_user_id: Option<String>,
     pub apm_user_identity: Option<String>,
     pub enabled_payment_methods: Option<Vec<PaymentMethod>>,
+    /// Lets the buyer split the price over several cards, see
+    /// [`MultiPayment`](crate::request::payment::multi_payment::MultiPayment)
+    pub allow_multi_payment: Option<bool>,
+}
+
+impl CheckoutPaymentInitiationRequest {
+    pub fn price(&self) -> Money {
+        Money {
+            amount: self.price,
+            currency: self.currency.clone(),
+        }
+    }
+
+    pub fn paid_price(&self) -> Money {
+        Money {
+            amount: self.paid_price,
+            currency: self.currency.clone(),
+        }
+    }
+}
+
+/// Installment counts Craftgate offers on the checkout form
+pub const INSTALLMENT_RANGE: RangeInclusive<u64> = 1..=12;
+/// Lifetime in seconds Craftgate accepts for a checkout token
+pub const TTL_RANGE: RangeInclusive<u64> = 60..=86_400;
+
+impl Validate for CheckoutPaymentInitiationRequest {
+    fn validate(&self) -> Result<(), Vec<Violation>> {
+        let mut violations = Vec::new();
+
+        check_amount(&mut violations, "price", &self.price());
+        check_amount(&mut violations, "paid_price", &self.paid_price());
+        if self.paid_price < self.price {
+            violations.push(Violation::new(
+                "paid_price",
+                ViolationKind::PaidPriceLowerThanPrice,
+            ));
+        }
+
+        if self.items.is_empty() {
+            violations.push(Violation::new("items", ViolationKind::Empty));
+        }
+        for (i, item) in self.items.iter().enumerate() {
+            check_amount(
+                &mut violations,
+                format!("items[{}].price", i),
+                &item.price_in(self.currency.clone()),
+            );
+            if let Some(sub_merchant_price) =
+                item.sub_merchant_member_price_in(self.currency.clone())
+            {
+                check_scale(
+                    &mut violations,
+                    format!("items[{}].sub_merchant_member_price", i),
+                    &sub_merchant_price,
+                );
+            }
+        }
+        let items = Money::checked_sum(
+            self.currency.clone(),
+            self.items
+                .iter()
+                .map(|item| item.price_in(self.currency.clone())),
+        );
+        if let Ok(items) = items {
+            if !self.items.is_empty() && items.amount != self.price {
+                violations.push(Violation::new(
+                    "items",
+                    ViolationKind::ItemSumMismatch {
+                        price: self.price,
+                        items: items.amount,
+                    },
+                ));
+            }
+        }
+
+        if Url::parse(&self.callback_url)
+            .map_or(true, |url| !matches!(url.scheme(), "http" | "https"))
+        {
+            violations.push(Violation::new("callback_url", ViolationKind::InvalidUrl));
+        }
+
+        if let Some(ttl) = self.ttl {
+            check_range(&mut violations, "ttl", ttl, TTL_RANGE);
+        }
+        for (i, &installment) in self.enabled_installments.iter().flatten().enumerate() {
+            check_range(
+                &mut violations,
+                format!("enabled_installments[{}]", i),
+                installment,
+                INSTALLMENT_RANGE,
+            );
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+fn check_amount(violations: &mut Vec<Violation>, field: impl Into<String>, amount: &Money) {
+    let field = field.into();
+    if amount.amount <= Decimal::ZERO {
+        violations.push(Violation::new(field.clone(), ViolationKind::NotPositive));
+    }
+    check_scale(violations, field, amount);
+}
+
+fn check_scale(violations: &mut Vec<Violation>, field: impl Into<String>, amount: &Money) {
+    if let Err(MoneyError::InvalidScale { scale, .. }) = amount.validate_scale() {
+        violations.push(Violation::new(field, ViolationKind::InvalidScale { scale }));
+    }
+}
+
+fn check_range(
+    violations: &mut Vec<Violation>,
+    field: impl Into<String>,
+    value: u64,
+    range: RangeInclusive<u64>,
+) {
+    if !range.contains(&value) {
+        violations.push(Violation::new(
+            field,
+            ViolationKind::OutOfRange {
+                min: *range.start(),
+                max: *range.end(),
+            },
+        ));
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CheckoutPaymentInitiationResponse {
-    pub token: String,
+    pub token: CheckoutToken,
     pub page_url: String,
-    // #[serde(with = "time::serde::rfc2822")]
-    pub token_expire_date: String,
+    #[serde(with = "crate::request::date::craftgate")]
+    pub token_expire_date: OffsetDateTime,
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use super::CheckoutPaymentInitiationRequest;
+    use crate::request::money::Money;
+    use crate::request::payment::PaymentItem;
+    use crate::request::validation::{Validate, Violation, ViolationKind};
+    use crate::request::Currency;
+
+    fn request(price: Decimal, item_prices: &[Decimal]) -> CheckoutPaymentInitiationRequest {
+        CheckoutPaymentInitiationRequest::builder()
+            .price(price)
+            .paid_price(price)
+            .callback_url("https://www.your-website.com/callback".to_owned())
+            .items(
+                item_prices
+                    .iter()
+                    .map(|&price| PaymentItem::builder().price(price).build())
+                    .collect(),
+            )
+            .build()
+    }
+
+    #[test]
+    fn item_prices_add_up_to_price() {
+        let request = request(
+            Decimal::new(100, 0),
+            &[
+                Decimal::new(30, 0),
+                Decimal::new(50, 0),
+                Decimal::new(20, 0),
+            ],
+        );
+
+        assert_eq!(request.validate(), Ok(()));
+    }
+
+    #[test]
+    fn item_prices_not_adding_up_are_rejected() {
+        let request = request(
+            Decimal::new(100, 0),
+            &[Decimal::new(30, 0), Decimal::new(50, 0)],
+        );
+
+        assert_eq!(
+            request.validate(),
+            Err(vec![Violation::new(
+                "items",
+                ViolationKind::ItemSumMismatch {
+                    price: Decimal::new(100, 0),
+                    items: Decimal::new(80, 0),
+                }
+            )])
+        );
+    }
+
+    #[test]
+    fn amounts_follow_the_currency_scale() {
+        let mut request = request(Decimal::new(10_005, 3), &[Decimal::new(10_005, 3)]);
+
+        assert_eq!(
+            request.validate(),
+            Err(vec![
+                Violation::new("price", ViolationKind::InvalidScale { scale: 2 }),
+                Violation::new("paid_price", ViolationKind::InvalidScale { scale: 2 }),
+                Violation::new("items[0].price", ViolationKind::InvalidScale { scale: 2 }),
+            ])
+        );
+
+        request.currency = Currency::Iqd;
+        assert_eq!(request.validate(), Ok(()));
+        assert_eq!(
+            request.paid_price(),
+            Money {
+                amount: Decimal::new(10_005, 3),
+                currency: Currency::Iqd
+            }
+        );
+    }
+
+    #[test]
+    fn valid_request_has_no_violations() {
+        let mut request = request(Decimal::new(100, 0), &[Decimal::new(100, 0)]);
+        request.ttl = Some(600);
+        request.enabled_installments = Some(vec![1, 3, 12]);
+
+        assert_eq!(request.validate(), Ok(()));
+    }
+
+    #[test]
+    fn every_violation_is_reported() {
+        let mut request = request(Decimal::new(100, 0), &[]);
+        request.paid_price = Decimal::new(90, 0);
+        request.callback_url = "your-website.com/callback".to_owned();
+        request.ttl = Some(10);
+        request.enabled_installments = Some(vec![0, 6, 13]);
+
+        assert_eq!(
+            request.validate(),
+            Err(vec![
+                Violation::new("paid_price", ViolationKind::PaidPriceLowerThanPrice),
+                Violation::new("items", ViolationKind::Empty),
+                Violation::new("callback_url", ViolationKind::InvalidUrl),
+                Violation::new(
+                    "ttl",
+                    ViolationKind::OutOfRange {
+                        min: 60,
+                        max: 86_400
+                    }
+                ),
+                Violation::new(
+                    "enabled_installments[0]",
+                    ViolationKind::OutOfRange { min: 1, max: 12 }
+                ),
+                Violation::new(
+                    "enabled_installments[2]",
+                    ViolationKind::OutOfRange { min: 1, max: 12 }
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn item_violations_name_the_item() {
+        let request = request(
+            Decimal::new(100, 0),
+            &[Decimal::new(100_005, 3), Decimal::ZERO],
+        );
+
+        assert_eq!(
+            request.validate(),
+            Err(vec![
+                Violation::new("items[0].price", ViolationKind::InvalidScale { scale: 2 }),
+                Violation::new("items[1].price", ViolationKind::NotPositive),
+                Violation::new(
+                    "items",
+                    ViolationKind::ItemSumMismatch {
+                        price: Decimal::new(100, 0),
+                        items: Decimal::new(100_005, 3),
+                    }
+                ),
+            ])
+        );
+    }
 }
@@ -0,0 +1,53 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::id::MemberId;
+use crate::request::money::Money;
+use crate::request::payment::{Card, PaymentGroup, PaymentItem, PaymentPhase};
+use crate::request::Currency;
+
+/// Payment collected from a card directly, without 3D Secure
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default))]
+pub struct CreatePaymentRequest {
+    #[builder(!default)]
+    pub price: Decimal,
+    #[builder(!default)]
+    pub paid_price: Decimal,
+    /// Amount collected from the buyer's wallet
+    pub wallet_price: Option<Decimal>,
+    #[builder(default = 1)]
+    pub installment: u64,
+    pub currency: Currency,
+    pub conversation_id: Option<String>,
+    pub external_id: Option<String>,
+    pub bank_order_id: Option<String>,
+    pub payment_group: PaymentGroup,
+    pub payment_phase: PaymentPhase,
+    pub payment_channel: Option<String>,
+    pub buyer_member_id: Option<MemberId>,
+    /// Alias of the POS the payment should be collected through
+    pub pos_alias: Option<String>,
+    #[builder(!default)]
+    pub card: Card,
+    #[builder(!default)]
+    pub items: Vec<PaymentItem>,
+}
+
+impl CreatePaymentRequest {
+    pub fn price(&self) -> Money {
+        Money {
+            amount: self.price,
+            currency: self.currency.clone(),
+        }
+    }
+
+    pub fn paid_price(&self) -> Money {
+        Money {
+            amount: self.paid_price,
+            currency: self.currency.clone(),
+        }
+    }
+}
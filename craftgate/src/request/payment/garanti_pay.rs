@@ -0,0 +1,63 @@
+//! Payments with Garanti Pay, the wallet of Garanti BBVA. The buyer is sent to Garanti Pay with
+//! the page of the init response and returns to `callback_url` with the result of the payment.
+
+use data_encoding::BASE64;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::id::{MemberId, PaymentId};
+use crate::request::payment::{PaymentGroup, PaymentItem, PaymentPhase};
+use crate::request::Currency;
+
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default))]
+pub struct InitGarantiPayPaymentRequest {
+    #[builder(!default)]
+    pub price: Decimal,
+    #[builder(!default)]
+    pub paid_price: Decimal,
+    pub currency: Currency,
+    pub conversation_id: Option<String>,
+    pub external_id: Option<String>,
+    pub bank_order_id: Option<String>,
+    pub payment_group: PaymentGroup,
+    pub payment_phase: PaymentPhase,
+    pub payment_channel: Option<String>,
+    pub buyer_member_id: Option<MemberId>,
+    /// Alias of the Garanti BBVA POS the payment should be collected through
+    pub pos_alias: Option<String>,
+    pub client_ip: Option<String>,
+    #[builder(!default)]
+    pub callback_url: String,
+    /// Installment options offered to the buyer on Garanti Pay
+    pub installments: Option<Vec<GarantiPayInstallment>>,
+    pub enable_installments: Option<bool>,
+    pub enable_loyalty: Option<bool>,
+    #[builder(!default)]
+    pub items: Vec<PaymentItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+pub struct GarantiPayInstallment {
+    pub number: u64,
+    /// Amount the buyer pays in total with this installment count
+    pub total_price: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InitGarantiPayPaymentResponse {
+    /// Base64 encoded HTML page that redirects the buyer to Garanti Pay
+    pub html_content: String,
+    pub payment_id: PaymentId,
+}
+
+impl InitGarantiPayPaymentResponse {
+    /// Decodes `html_content`
+    pub fn html(&self) -> Result<Vec<u8>, data_encoding::DecodeError> {
+        BASE64.decode(self.html_content.as_bytes())
+    }
+}
@@ -0,0 +1,112 @@
+//! Payments with cards stored in Masterpass. The buyer's Masterpass account is checked with
+//! their phone number, a payment token is generated for the chosen card and the payment is
+//! completed with it after Masterpass verifies the buyer, directly or with 3D Secure.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::id::MemberId;
+use crate::request::payment::{PaymentGroup, PaymentItem, PaymentPhase};
+use crate::request::Currency;
+
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckMasterpassUserRequest {
+    /// Phone number of the buyer, e.g. `905551111111`
+    pub masterpass_gsm_number: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckMasterpassUserResponse {
+    /// Whether the buyer has a Masterpass account with cards that can be used
+    pub is_eligible_to_use_masterpass: bool,
+    /// Whether the account is linked to another merchant
+    pub is_another_merchant_account: Option<bool>,
+    pub account_status: Option<String>,
+}
+
+/// Payment details of a Masterpass payment token
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default))]
+pub struct MasterpassCreatePayment {
+    #[builder(!default)]
+    pub price: Decimal,
+    #[builder(!default)]
+    pub paid_price: Decimal,
+    #[builder(default = 1)]
+    pub installment: u64,
+    pub currency: Currency,
+    pub conversation_id: Option<String>,
+    pub external_id: Option<String>,
+    pub bank_order_id: Option<String>,
+    pub payment_group: PaymentGroup,
+    pub payment_phase: PaymentPhase,
+    pub payment_channel: Option<String>,
+    pub buyer_member_id: Option<MemberId>,
+    #[builder(!default)]
+    pub items: Vec<PaymentItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default))]
+pub struct MasterpassPaymentTokenGenerateRequest {
+    /// ID of the buyer at the merchant
+    #[builder(!default)]
+    pub user_id: String,
+    /// Phone number of the buyer's Masterpass account
+    #[builder(!default)]
+    pub msisdn: String,
+    /// First digits of the chosen card
+    #[builder(!default)]
+    pub bin_number: String,
+    pub force_three_d_s: Option<bool>,
+    #[builder(!default)]
+    pub create_payment: MasterpassCreatePayment,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MasterpassPaymentTokenGenerateResponse {
+    /// Token the Masterpass SDK of the buyer's device completes the card selection with
+    pub token: String,
+    pub reference_id: String,
+    pub order_no: Option<String>,
+    pub terminal_group_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+pub struct MasterpassPaymentCompleteRequest {
+    pub reference_id: String,
+    /// Token Masterpass sent after verifying the buyer
+    pub token: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+pub struct MasterpassPaymentThreeDSInitRequest {
+    pub reference_id: String,
+    pub callback_url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MasterpassPaymentThreeDSInitResponse {
+    /// URL the buyer is redirected to for 3D Secure
+    pub return_url: String,
+}
+
+/// Card stored in Masterpass to look up reward points of, see
+/// [`RetrieveLoyaltiesRequest`](crate::request::payment::retrieve_loyalties::RetrieveLoyaltiesRequest)
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+pub struct MasterpassRetrieveLoyaltiesRequest {
+    pub msisdn: String,
+    /// Name of the card in the buyer's Masterpass account
+    pub card_name: String,
+    pub bin_number: String,
+}
@@ -1,14 +1,26 @@
+use crate::id::{CardToken, CardUserKey, MemberId, PaymentId, PaymentTransactionId};
+use crate::request::money::Money;
 use crate::request::{CardType, Currency};
 use rust_decimal::Decimal;
-use serde::{Deserialize, Serialize};
-use serde_repr::{Deserialize_repr, Serialize_repr};
-use time::PrimitiveDateTime;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_enum_str::{Deserialize_enum_str, Serialize_enum_str};
+use time::OffsetDateTime;
 use typed_builder::TypedBuilder;
 
+pub mod apple_pay;
+pub mod bkm_express;
 pub mod checkout_payment;
+pub mod create_payment;
+pub mod garanti_pay;
+pub mod masterpass;
+pub mod multi_payment;
+pub mod retrieve_loyalties;
+pub mod search_payments;
+pub mod three_ds_payment;
+pub mod transaction_refund;
 
 /// Values expressing the way a payment is collected
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PaymentType {
     /// Payment fully deducted from card
@@ -21,10 +33,14 @@ pub enum PaymentType {
     CardAndWalletPayment,
     /// Bank transfer
     BankTransfer,
+    /// Payment collected through an alternative payment method, e.g. Papara or Klarna
+    Apm,
+    #[serde(other)]
+    Unhandled(String),
 }
 
 /// Group names expressing the product or service to which the payment is done
-#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Default, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PaymentGroup {
     /// Product
@@ -32,10 +48,12 @@ pub enum PaymentGroup {
     Product,
     /// Ad, listing, service or subscription
     ListingOrSubscription,
+    #[serde(other)]
+    Unhandled(String),
 }
 
 /// Values expressing the status of the payment
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PaymentStatus {
     /// Payment is failed
@@ -47,20 +65,31 @@ pub enum PaymentStatus {
     /// After entering the sms code on the bank's 3D Secure SMS page, the transaction is waiting
     /// for buyer return to the callback address of merchant and complete the payment
     CallbackThreeds,
+    #[serde(other)]
+    Unhandled(String),
+}
+
+impl PaymentStatus {
+    /// Whether the payment is completed, successfully or not, and its status won't change anymore
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, PaymentStatus::Success | PaymentStatus::Failure)
+    }
 }
 
 /// Values expressing approval status for a payment item
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PaymentItemConfirmationStatus {
     /// Awaiting approval
     WaitingForApproval,
     /// Approved
     Approved,
+    #[serde(other)]
+    Unhandled(String),
 }
 
 /// Transaction type/phase of the payment at the bank
-#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Default, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PaymentPhase {
     /// In standard provision transaction, the payment is set as this status value
@@ -72,10 +101,12 @@ pub enum PaymentPhase {
     PreAuth,
     /// In case of capture for a pre-authorized transaction, the payment phase takes this value
     PostAuth,
+    #[serde(other)]
+    Unhandled(String),
 }
 
 /// Users can pay below methods from Common Payment Page/Form
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PaymentMethod {
     /// User pays with card
@@ -100,10 +131,12 @@ pub enum PaymentMethod {
     Klarna,
     /// User pays with own Stripe account
     Stripe,
+    #[serde(other)]
+    Unhandled(String),
 }
 
 /// Values expressing the Payout status
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PayoutStatus {
     /// Money transfer will not be done
@@ -114,10 +147,12 @@ pub enum PayoutStatus {
     PayoutStarted,
     /// Money transfer completed
     PayoutCompleted,
+    #[serde(other)]
+    Unhandled(String),
 }
 
 /// Values expressing Payout Types
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PayoutType {
     /// Represents money transfers due to Payment/Cancellation/Return transactions
@@ -127,10 +162,12 @@ pub enum PayoutType {
     BouncedSettlement,
     /// Represents money transfers based on sub-merchants' wallet balance withdrawal requests
     Withdraw,
+    #[serde(other)]
+    Unhandled(String),
 }
 
 /// Values expressing the Payout Source
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PayoutSource {
     /// Represents money transfers due to Payment/Cancellation/Return transactions
@@ -140,33 +177,80 @@ pub enum PayoutSource {
     Bounced,
     /// Represents money transfers based on your sub-merchants' wallet balance withdrawal requests
     Withdraw,
+    #[serde(other)]
+    Unhandled(String),
 }
 
 /// These are the numerical values transmitted to Craftgate by the bank after verification for
 /// payments made with 3D Secure. Although banks and payment institutions sometimes return with
 /// their unique MD Status figures, the values in the table below can be accepted as standard.
-#[derive(Serialize_repr, Deserialize_repr, PartialEq, Debug, Clone)]
-#[repr(u8)]
+#[derive(PartialEq, Debug, Clone)]
 pub enum MdStatus {
     /// 3D Secure verification or signature invalid
-    VerificationOrSignatureInvalid = 0,
+    VerificationOrSignatureInvalid,
     /// Full Verification, processing can be continued
-    FullVerification = 1,
+    FullVerification,
     /// Card holder or bank not registered in the system
-    CardholderOrBankNotRegistered = 2,
+    CardholderOrBankNotRegistered,
     /// The bank of the card is not registered in the system
-    BankNotRegistered = 3,
+    BankNotRegistered,
     /// Verification attempt, cardholder has chosen to register later in the system
-    VerificationAttempt = 4,
+    VerificationAttempt,
     /// Unable to verify
-    UnableToVerify = 5,
+    UnableToVerify,
     /// 3D Secure error
-    Error = 6,
+    Error,
     /// System error
-    SystemError = 7,
+    SystemError,
+    /// A value unique to the bank or payment institution
+    Unhandled(u8),
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+impl MdStatus {
+    pub fn code(&self) -> u8 {
+        match self {
+            MdStatus::VerificationOrSignatureInvalid => 0,
+            MdStatus::FullVerification => 1,
+            MdStatus::CardholderOrBankNotRegistered => 2,
+            MdStatus::BankNotRegistered => 3,
+            MdStatus::VerificationAttempt => 4,
+            MdStatus::UnableToVerify => 5,
+            MdStatus::Error => 6,
+            MdStatus::SystemError => 7,
+            MdStatus::Unhandled(code) => *code,
+        }
+    }
+}
+
+impl From<u8> for MdStatus {
+    fn from(code: u8) -> Self {
+        match code {
+            0 => MdStatus::VerificationOrSignatureInvalid,
+            1 => MdStatus::FullVerification,
+            2 => MdStatus::CardholderOrBankNotRegistered,
+            3 => MdStatus::BankNotRegistered,
+            4 => MdStatus::VerificationAttempt,
+            5 => MdStatus::UnableToVerify,
+            6 => MdStatus::Error,
+            7 => MdStatus::SystemError,
+            code => MdStatus::Unhandled(code),
+        }
+    }
+}
+
+impl Serialize for MdStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for MdStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u8::deserialize(deserializer).map(MdStatus::from)
+    }
+}
+
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum CardAssociation {
     Visa,
@@ -178,10 +262,12 @@ pub enum CardAssociation {
     Maestro,
     Discover,
     DinersClub,
+    #[serde(other)]
+    Unhandled(String),
 }
 
 /// Indicated which type of integration a payment is coming from
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PaymentSource {
     /// via API
@@ -190,6 +276,10 @@ pub enum PaymentSource {
     Masterpass,
     /// via Common Payment Form
     CheckoutForm,
+    /// via payment link
+    PayByLink,
+    #[serde(other)]
+    Unhandled(String),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, TypedBuilder)]
@@ -200,11 +290,28 @@ pub struct PaymentItem {
     #[builder(!default)]
     pub price: Decimal,
     pub external_id: Option<String>,
-    pub sub_merchant_member_id: Option<String>,
-    pub sub_merchant_member_price: Option<String>,
+    pub sub_merchant_member_id: Option<MemberId>,
+    pub sub_merchant_member_price: Option<Decimal>,
+}
+
+impl PaymentItem {
+    /// Price of the item in the `currency` of the payment it belongs to
+    pub fn price_in(&self, currency: Currency) -> Money {
+        Money {
+            amount: self.price,
+            currency,
+        }
+    }
+
+    /// Share of the sub-merchant in the `currency` of the payment the item belongs to
+    pub fn sub_merchant_member_price_in(&self, currency: Currency) -> Option<Money> {
+        self.sub_merchant_member_price
+            .map(|amount| Money { amount, currency })
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct MerchantPos {
     /// ID of the POS
     pub id: u64,
@@ -216,7 +323,7 @@ pub struct MerchantPos {
     pub bank_id: u64,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum LoyaltyType {
     RewardMoney,
@@ -225,6 +332,8 @@ pub enum LoyaltyType {
     ExtraPoints,
     GainingMinutes,
     PostponingStatement,
+    #[serde(other)]
+    Unhandled(String),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
@@ -234,36 +343,87 @@ pub struct Reward {
     pub firm_reward_money: Decimal,
 }
 
+/// Reward points of a card. Returned by `CraftgateClient::retrieve_loyalties` and used in a
+/// payment by setting it on the [`Card`].
 #[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
 pub struct Loyalty {
     #[serde(rename = "type")]
     pub loyalty_type: LoyaltyType,
     #[builder(default)]
     pub reward: Option<Reward>,
+    /// Message of the bank about the reward
+    #[builder(default)]
+    pub message: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// Wallets on the buyer's device that hand out an encrypted card instead of the card details
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WalletTokenType {
+    ApplePay,
+    GooglePay,
+    #[serde(other)]
+    Unhandled(String),
+}
+
+/// Encrypted card returned by Apple Pay or Google Pay. Craftgate decrypts it, so the token is
+/// sent as the wallet returned it.
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+pub struct WalletToken {
+    #[serde(rename = "type")]
+    pub token_type: WalletTokenType,
+    /// Payment data of the token, e.g. `paymentData` of an Apple Pay token serialized as JSON
+    pub token: String,
+}
+
+/// Card a payment is collected from. Either the card details, the `card_user_key` and
+/// `card_token` of a stored card or a `wallet_token` are set.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default))]
+pub struct Card {
+    pub card_holder_name: Option<String>,
+    pub card_number: Option<String>,
+    pub expire_year: Option<String>,
+    pub expire_month: Option<String>,
+    pub cvc: Option<String>,
+    /// Alias of the card when it is stored after the payment
+    pub card_alias: Option<String>,
+    pub card_user_key: Option<CardUserKey>,
+    pub card_token: Option<CardToken>,
+    pub store_card_after_success_payment: Option<bool>,
+    /// Reward points to use in the payment
+    pub loyalty: Option<Loyalty>,
+    /// Encrypted card from Apple Pay or Google Pay
+    pub wallet_token: Option<WalletToken>,
+}
+
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum FraudAction {
     Block,
     Review,
+    #[serde(other)]
+    Unhandled(String),
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TransactionStatus {
     WaitingForApproval,
     Approved,
+    #[serde(other)]
+    Unhandled(String),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Payment {
     /// ID of the payment.
-    pub id: u64,
+    pub id: PaymentId,
     /// The date that payment is created
-    #[serde(with = "crate::request::craftgate")]
-    pub created_date: PrimitiveDateTime,
+    #[serde(with = "crate::request::date::craftgate")]
+    pub created_date: OffsetDateTime,
     /// Basket price of the payment
     pub price: Decimal,
     /// Paid price of the payment
@@ -272,7 +432,7 @@ pub struct Payment {
     pub wallet_price: Decimal,
     pub currency: Currency,
     /// Buyer member ID of the payment
-    pub buyer_member_id: Option<u64>,
+    pub buyer_member_id: Option<MemberId>,
     /// Installment number
     pub installment: u64,
     /// Value of the `conversation_id` parameter sent in the payment request
@@ -297,35 +457,38 @@ pub struct Payment {
     /// Bank commission rate amount
     pub bank_commission_rate_amount: Decimal,
     /// Card user key that represents the card holder.
-    pub card_user_key: Option<String>,
+    pub card_user_key: Option<CardUserKey>,
     /// Card token that represents the card.
-    pub card_token: Option<String>,
+    pub card_token: Option<CardToken>,
     /// Indicates whether the payment was made with a stored card
     pub paid_with_stored_card: bool,
     /// Exist for the payments fully or partially collected from the card. First 8 digits of the card
-    pub bin_number: String,
+    pub bin_number: Option<String>,
     /// Exist for the payments fully or partially collected from the card. Last 4 digits of the card
-    pub last_four_digits: String,
-    /// Card holder name surname
-    pub card_holder_name: String,
+    pub last_four_digits: Option<String>,
+    /// Card holder name surname. Exist for the payments fully or partially collected from the card
+    pub card_holder_name: Option<String>,
     /// Card holder name surname retrieved from bank. The return value might be null for PF poses
-    pub bank_card_holder_name: String,
+    pub bank_card_holder_name: Option<String>,
     /// authCode value retrieved from bank related to the payment. The return value might be null for PF poses
     pub auth_code: Option<String>,
-    /// hostReference value retrieved from bank related to the payment
-    pub host_reference: String,
+    /// hostReference value retrieved from bank related to the payment. The return value might be null for PF poses
+    pub host_reference: Option<String>,
     /// transId value retrieved from bank related to the payment. The return value might be null for PF poses
     pub trans_id: Option<String>,
-    /// orderId value retrieved from bank related to the payment
-    pub order_id: String,
-    pub card_type: CardType,
-    pub card_association: CardAssociation,
+    /// orderId value retrieved from bank related to the payment. The return value might be null for PF poses
+    pub order_id: Option<String>,
+    /// Exist for the payments fully or partially collected from the card
+    pub card_type: Option<CardType>,
+    /// Exist for the payments fully or partially collected from the card
+    pub card_association: Option<CardAssociation>,
     /// Brand of card. Exist for the payments fully or partially collected from the card
     pub card_brand: Option<String>,
     /// Value of the `pos_alias` parameter sent in the payment request
     pub requested_pos_alias: Option<String>,
-    /// POS info that payment is received from
-    pub pos: MerchantPos,
+    /// POS info that payment is received from. Null for payments not collected through a POS, e.g.
+    /// wallet and APM payments
+    pub pos: Option<MerchantPos>,
     /// Loyalty info that used in payment
     pub loyalty: Option<Loyalty>,
     /// Fraud Check if fraud rule matches
@@ -339,11 +502,34 @@ pub struct Payment {
     pub additional_data: Option<AdditionalData>,
 }
 
+impl Payment {
+    pub fn price(&self) -> Money {
+        Money {
+            amount: self.price,
+            currency: self.currency.clone(),
+        }
+    }
+
+    pub fn paid_price(&self) -> Money {
+        Money {
+            amount: self.paid_price,
+            currency: self.currency.clone(),
+        }
+    }
+
+    pub fn wallet_price(&self) -> Money {
+        Money {
+            amount: self.wallet_price,
+            currency: self.currency.clone(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct PaymentTransaction {
     /// ID of the payment transaction
-    pub id: u64,
+    pub id: PaymentTransactionId,
     /// External ID of the payment item that is sent in the request
     pub external_id: Option<String>,
     /// Name of the payment item that is sent in the request
@@ -361,7 +547,7 @@ pub struct PaymentTransaction {
     /// Merchant's payout amount of the payment transaction
     pub merchant_payout_amount: Decimal,
     /// ID of the sub-merchant
-    pub sub_merchant_member_id: Option<u64>,
+    pub sub_merchant_member_id: Option<MemberId>,
     /// Sub-merchant requested payout amount for the payment item.
     pub sub_merchant_member_price: Decimal,
     /// Sub-merchant payout rate for the payment item.
@@ -371,8 +557,9 @@ pub struct PaymentTransaction {
     /// Status of payment transaction confirmation
     pub transaction_status: TransactionStatus,
     /// Date that the blockage will be resolved
-    #[serde(with = "crate::request::craftgate")]
-    pub blockage_resolved_date: PrimitiveDateTime,
+    #[serde(with = "crate::request::date::craftgate::option")]
+    #[serde(default)]
+    pub blockage_resolved_date: Option<OffsetDateTime>,
     /// It includes the distribution of the money transfer to be made in relation to the payment,
     /// in a transaction specific, between the merchant and the sub-merchant. The currency of the
     /// money distribution is always `Currency::Try`
@@ -403,3 +590,106 @@ pub struct AdditionalData {
     #[serde(flatten)]
     pub rest: serde_json::Value,
 }
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use serde_json::json;
+
+    use super::{CardAssociation, LoyaltyType, MdStatus, Payment, PaymentSource, PaymentStatus};
+    use crate::request::Currency;
+
+    #[test]
+    fn payment_with_unknown_values_decodes() {
+        let payment: Payment = serde_json::from_value(json!({
+            "id": 1,
+            "createdDate": "2023-10-27T14:03:01",
+            "price": 100,
+            "paidPrice": 100,
+            "walletPrice": 0,
+            "currency": "JPY",
+            "buyerMemberId": null,
+            "installment": 1,
+            "conversationId": "456d1297-908e-4bd6-a13b-4be31a6e47d5",
+            "externalId": null,
+            "paymentType": "CARD_PAYMENT",
+            "paymentGroup": "PRODUCT",
+            "paymentSource": "SOFT_POS",
+            "paymentStatus": "WAITING_FOR_REVIEW",
+            "paymentPhase": "AUTH",
+            "paymentChannel": null,
+            "isThreeDS": false,
+            "merchantCommissionRate": 0,
+            "merchantCommissionRateAmount": 0,
+            "bankCommissionRate": 0.01,
+            "bankCommissionRateAmount": 1,
+            "cardUserKey": null,
+            "cardToken": null,
+            "paidWithStoredCard": false,
+            "binNumber": "52600100",
+            "lastFourDigits": "0026",
+            "cardHolderName": "Haluk Demir",
+            "bankCardHolderName": "Haluk Demir",
+            "authCode": "867440",
+            "hostReference": "host-reference",
+            "transId": "trans-id",
+            "orderId": "order-id",
+            "cardType": "CREDIT_CARD",
+            "cardAssociation": "ELO",
+            "cardBrand": null,
+            "requestedPosAlias": null,
+            "pos": { "id": 1, "name": "POS", "alias": "pos", "bankId": 1 },
+            "loyalty": { "type": "CASHBACK" },
+            "fraudId": null,
+            "fraudAction": null,
+            "paymentTransactions": [],
+            "additionalData": null
+        }))
+        .unwrap();
+
+        assert_matches!(payment.currency, Currency::Unhandled(value) => {
+            assert_eq!(value, "JPY");
+        });
+        assert_matches!(payment.payment_source, PaymentSource::Unhandled(value) => {
+            assert_eq!(value, "SOFT_POS");
+        });
+        assert_matches!(payment.payment_status, PaymentStatus::Unhandled(value) => {
+            assert_eq!(value, "WAITING_FOR_REVIEW");
+        });
+        assert_matches!(payment.card_association, Some(CardAssociation::Unhandled(value)) => {
+            assert_eq!(value, "ELO");
+        });
+        assert_matches!(payment.loyalty.unwrap().loyalty_type, LoyaltyType::Unhandled(value) => {
+            assert_eq!(value, "CASHBACK");
+        });
+    }
+
+    #[test]
+    fn unknown_values_are_sent_unchanged() {
+        assert_eq!(
+            serde_json::to_value(PaymentStatus::Unhandled("WAITING_FOR_REVIEW".to_owned()))
+                .unwrap(),
+            json!("WAITING_FOR_REVIEW")
+        );
+        assert_eq!(
+            serde_json::to_value(PaymentStatus::CallbackThreeds).unwrap(),
+            json!("CALLBACK_THREEDS")
+        );
+    }
+
+    #[test]
+    fn md_status_keeps_bank_specific_values() {
+        assert_eq!(
+            serde_json::from_value::<MdStatus>(json!(1)).unwrap(),
+            MdStatus::FullVerification
+        );
+        assert_eq!(
+            serde_json::from_value::<MdStatus>(json!(9)).unwrap(),
+            MdStatus::Unhandled(9)
+        );
+        assert_eq!(
+            serde_json::to_value(MdStatus::Unhandled(9)).unwrap(),
+            json!(9)
+        );
+    }
+}
@@ -0,0 +1,40 @@
+//! Payments of one basket split over several cards. A checkout form initiated with
+//! `allow_multi_payment` lets the buyer pay part of the price with each card until nothing
+//! remains, and the payments made so far are retrieved with the token of the form.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_enum_str::{Deserialize_enum_str, Serialize_enum_str};
+use time::OffsetDateTime;
+
+use crate::id::{CheckoutToken, MultiPaymentId, PaymentId};
+
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MultiPaymentStatus {
+    /// Part of the price is still to be paid
+    Created,
+    /// The whole price is paid
+    Completed,
+    #[serde(other)]
+    Unhandled(String),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiPayment {
+    pub id: MultiPaymentId,
+    pub multi_payment_status: MultiPaymentStatus,
+    pub token: CheckoutToken,
+    pub conversation_id: Option<String>,
+    pub external_id: Option<String>,
+    /// Amount paid with all payments so far
+    pub paid_price: Decimal,
+    /// Amount of the price that is still to be paid
+    pub remaining_amount: Decimal,
+    #[serde(with = "crate::request::date::craftgate")]
+    pub token_expire_date: OffsetDateTime,
+    /// Payments made so far, retrieved with
+    /// [`retrieve_payment`](crate::client::CraftgateClient::retrieve_payment)
+    pub payment_ids: Vec<PaymentId>,
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::id::{CardToken, CardUserKey};
+use crate::request::payment::{Loyalty, MerchantPos};
+
+/// Card to look up reward points of. Either the card details or the `card_user_key` and
+/// `card_token` of a stored card are set.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default))]
+pub struct RetrieveLoyaltiesRequest {
+    pub card_number: Option<String>,
+    pub expire_year: Option<String>,
+    pub expire_month: Option<String>,
+    pub cvc: Option<String>,
+    pub card_user_key: Option<CardUserKey>,
+    pub card_token: Option<CardToken>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RetrieveLoyaltiesResponse {
+    pub card_brand: Option<String>,
+    /// Whether payments with the card must be made with 3D Secure
+    #[serde(rename = "force3ds")]
+    pub force_three_d_s: Option<bool>,
+    /// POS the rewards are available on
+    pub pos: Option<MerchantPos>,
+    #[serde(default)]
+    pub loyalties: Vec<Loyalty>,
+}
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use typed_builder::TypedBuilder;
+
+use crate::id::MemberId;
+use crate::request::payment::{PaymentStatus, PaymentType};
+use crate::request::Currency;
+
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default))]
+pub struct SearchPaymentsRequest {
+    #[builder(default = 0)]
+    pub page: u64,
+    #[builder(default = 25)]
+    pub size: u64,
+    /// Value of the `conversation_id` parameter sent in the payment request
+    pub conversation_id: Option<String>,
+    /// `external_id` value that sent in payment request by merchant
+    pub external_id: Option<String>,
+    pub buyer_member_id: Option<MemberId>,
+    pub payment_type: Option<PaymentType>,
+    pub payment_status: Option<PaymentStatus>,
+    pub currency: Option<Currency>,
+    #[serde(with = "crate::request::date::craftgate::option")]
+    #[serde(default)]
+    pub min_created_date: Option<OffsetDateTime>,
+    #[serde(with = "crate::request::date::craftgate::option")]
+    #[serde(default)]
+    pub max_created_date: Option<OffsetDateTime>,
+}
+
+impl Default for SearchPaymentsRequest {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::SearchPaymentsRequest;
+
+    #[test]
+    fn date_range_is_sent_in_craftgate_offset() {
+        let request = SearchPaymentsRequest::builder()
+            .min_created_date(Some(datetime!(2023-10-24 08:00:00 UTC)))
+            .max_created_date(Some(datetime!(2023-10-25 00:00:00 +3)))
+            .build();
+
+        assert_eq!(
+            serde_urlencoded::to_string(&request).unwrap(),
+            "page=0&size=25&minCreatedDate=2023-10-24T11%3A00%3A00\
+             &maxCreatedDate=2023-10-25T00%3A00%3A00"
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use data_encoding::BASE64;
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::id::PaymentId;
+use crate::request::payment::create_payment::CreatePaymentRequest;
+use crate::request::payment::PaymentStatus;
+
+/// Payment collected from a card with 3D Secure. The buyer is sent to the bank with the
+/// `html_content` of the response, and returns to `callback_url` to complete the payment.
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+pub struct InitThreeDSPaymentRequest {
+    #[serde(flatten)]
+    pub payment: CreatePaymentRequest,
+    pub callback_url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InitThreeDSPaymentResponse {
+    /// Base64 encoded HTML page that redirects the buyer to the bank
+    pub html_content: String,
+    pub payment_id: PaymentId,
+    pub payment_status: PaymentStatus,
+}
+
+impl InitThreeDSPaymentResponse {
+    /// Decodes `html_content`
+    pub fn html(&self) -> Result<Vec<u8>, data_encoding::DecodeError> {
+        BASE64.decode(self.html_content.as_bytes())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CompleteThreeDSPaymentRequest {
+    pub payment_id: PaymentId,
+}
@@ -0,0 +1,61 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_enum_str::{Deserialize_enum_str, Serialize_enum_str};
+use time::OffsetDateTime;
+use typed_builder::TypedBuilder;
+
+use crate::id::{PaymentId, PaymentTransactionId, PaymentTransactionRefundId};
+use crate::request::Currency;
+
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RefundStatus {
+    Success,
+    Failure,
+    #[serde(other)]
+    Unhandled(String),
+}
+
+/// Refund of a single payment transaction, made on its own or as part of a payment refund
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentTransactionRefund {
+    pub id: PaymentTransactionRefundId,
+    #[serde(with = "crate::request::date::craftgate")]
+    pub created_date: OffsetDateTime,
+    pub status: RefundStatus,
+    /// Whether the transaction was already paid out when it was refunded
+    pub is_after_settlement: bool,
+    pub refund_price: Decimal,
+    pub conversation_id: Option<String>,
+    pub payment_id: PaymentId,
+    pub payment_transaction_id: PaymentTransactionId,
+    pub currency: Currency,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default))]
+pub struct SearchPaymentTransactionRefundsRequest {
+    #[builder(default = 0)]
+    pub page: u64,
+    #[builder(default = 25)]
+    pub size: u64,
+    pub payment_id: Option<PaymentId>,
+    pub payment_transaction_id: Option<PaymentTransactionId>,
+    pub conversation_id: Option<String>,
+    pub status: Option<RefundStatus>,
+    pub currency: Option<Currency>,
+    #[serde(with = "crate::request::date::craftgate::option")]
+    #[serde(default)]
+    pub min_created_date: Option<OffsetDateTime>,
+    #[serde(with = "crate::request::date::craftgate::option")]
+    #[serde(default)]
+    pub max_created_date: Option<OffsetDateTime>,
+}
+
+impl Default for SearchPaymentTransactionRefundsRequest {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use serde_enum_str::{Deserialize_enum_str, Serialize_enum_str};
+use time::Date;
+
+pub mod transaction_report;
+
+/// Format a report file is downloaded in
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReportFileType {
+    Csv,
+    Xlsx,
+    #[serde(other)]
+    Unhandled(String),
+}
+
+/// Query of a daily report file
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DailyReportQuery {
+    #[serde(with = "crate::request::date::day")]
+    pub report_date: Date,
+    pub file_type: ReportFileType,
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::{DailyReportQuery, ReportFileType};
+
+    #[test]
+    fn query_has_the_report_day() {
+        let query = DailyReportQuery {
+            report_date: date!(2023 - 11 - 07),
+            file_type: ReportFileType::Csv,
+        };
+
+        assert_eq!(
+            serde_urlencoded::to_string(&query).unwrap(),
+            "reportDate=2023-11-07&fileType=CSV"
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! Rows of the CSV daily transaction report, see
+//! [`CraftgateClient::retrieve_daily_transaction_report`](crate::client::CraftgateClient::retrieve_daily_transaction_report).
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use time::{OffsetDateTime, UtcOffset};
+
+use crate::id::{MemberId, PaymentId, PaymentTransactionId};
+use crate::request::date::{self, DEFAULT_OFFSET};
+use crate::request::payment::{PaymentStatus, PaymentType};
+use crate::request::Currency;
+
+/// A payment transaction in the daily transaction report. Columns of the report that are not
+/// listed here are ignored.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionReportRow {
+    pub payment_id: PaymentId,
+    pub payment_transaction_id: Option<PaymentTransactionId>,
+    #[serde(with = "crate::request::date::craftgate")]
+    pub created_date: OffsetDateTime,
+    pub payment_type: PaymentType,
+    pub payment_status: PaymentStatus,
+    pub currency: Currency,
+    #[serde(deserialize_with = "decimal::deserialize")]
+    pub price: Decimal,
+    #[serde(deserialize_with = "decimal::deserialize")]
+    pub paid_price: Decimal,
+    pub installment: Option<u64>,
+    pub conversation_id: Option<String>,
+    #[serde(deserialize_with = "decimal::option::deserialize")]
+    pub merchant_payout_amount: Option<Decimal>,
+    pub sub_merchant_member_id: Option<MemberId>,
+    #[serde(deserialize_with = "decimal::option::deserialize")]
+    pub sub_merchant_member_payout_amount: Option<Decimal>,
+}
+
+/// Parses a daily transaction report downloaded as [`ReportFileType::Csv`](super::ReportFileType::Csv),
+/// reading its timestamps in [`DEFAULT_OFFSET`]
+pub fn parse_transaction_report(report: &[u8]) -> Result<Vec<TransactionReportRow>, csv::Error> {
+    parse_transaction_report_with_offset(report, DEFAULT_OFFSET)
+}
+
+/// Parses a daily transaction report of a client created with another UTC offset
+pub fn parse_transaction_report_with_offset(
+    report: &[u8],
+    offset: UtcOffset,
+) -> Result<Vec<TransactionReportRow>, csv::Error> {
+    let report = report.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(report);
+
+    date::with_offset(offset, || {
+        csv::Reader::from_reader(report).deserialize().collect()
+    })
+}
+
+/// CSV fields are read as text, `Decimal`'s own deserializer would read them as floats and lose
+/// precision.
+mod decimal {
+    use std::str::FromStr;
+
+    use rust_decimal::Decimal;
+    use serde::{de, Deserialize, Deserializer};
+
+    pub fn deserialize<'a, D: Deserializer<'a>>(deserializer: D) -> Result<Decimal, D::Error> {
+        let value = String::deserialize(deserializer)?;
+
+        Decimal::from_str(&value).map_err(de::Error::custom)
+    }
+
+    pub mod option {
+        use std::str::FromStr;
+
+        use rust_decimal::Decimal;
+        use serde::{de, Deserialize, Deserializer};
+
+        pub fn deserialize<'a, D: Deserializer<'a>>(
+            deserializer: D,
+        ) -> Result<Option<Decimal>, D::Error> {
+            Option::<String>::deserialize(deserializer)?
+                .filter(|value| !value.is_empty())
+                .map(|value| Decimal::from_str(&value).map_err(de::Error::custom))
+                .transpose()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use rust_decimal::Decimal;
+
+    use super::parse_transaction_report;
+    use crate::id::{MemberId, PaymentId};
+    use crate::request::payment::PaymentStatus;
+
+    #[test]
+    fn rows_are_read_by_column_name() {
+        let report = "\u{feff}paymentId,createdDate,paymentStatus,paymentType,currency,price,\
+                      paidPrice,paymentTransactionId,merchantPayoutAmount,subMerchantMemberId,\
+                      subMerchantMemberPayoutAmount,installment,conversationId,cardHolderName\n\
+                      1045213,2023-11-07T10:15:42,SUCCESS,CARD_PAYMENT,TRY,100.10,100.10,\
+                      2091,4.61,116213,95.49,1,conv-1,REDACTED\n\
+                      1045214,2023-11-07T11:02:10,FAILURE,CARD_PAYMENT,TRY,50,50,,,,,1,,\n";
+
+        let rows = parse_transaction_report(report.as_bytes()).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].payment_id, PaymentId(1045213));
+        assert_eq!(rows[0].price, Decimal::new(10_010, 2));
+        assert_eq!(rows[0].merchant_payout_amount, Some(Decimal::new(4_61, 2)));
+        assert_eq!(rows[0].sub_merchant_member_id, Some(MemberId(116213)));
+        assert_matches!(rows[1].payment_status, PaymentStatus::Failure);
+        assert!(rows[1].payment_transaction_id.is_none());
+        assert!(rows[1].merchant_payout_amount.is_none());
+        assert!(rows[1].conversation_id.is_none());
+    }
+}
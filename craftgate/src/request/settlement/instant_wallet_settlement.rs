@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::id::MemberId;
+use crate::request::settlement::SettlementResultStatus;
+
+/// Settles the wallet balances of sub-merchants now instead of on their settlement day
+#[derive(Serialize, Deserialize, Debug, Clone, Default, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default))]
+pub struct CreateInstantWalletSettlementRequest {
+    /// Sub-merchants whose wallets should not be settled
+    pub excluded_sub_merchant_member_ids: Vec<MemberId>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SettlementResponse {
+    pub settlement_result_status: SettlementResultStatus,
+}
@@ -0,0 +1,17 @@
+use serde_enum_str::{Deserialize_enum_str, Serialize_enum_str};
+
+pub mod instant_wallet_settlement;
+pub mod payout_account;
+pub mod payout_completed_transaction;
+
+/// Outcome of a settlement triggered through the API
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SettlementResultStatus {
+    Success,
+    Failure,
+    /// There was nothing to settle
+    NoRecordFound,
+    #[serde(other)]
+    Unhandled(String),
+}
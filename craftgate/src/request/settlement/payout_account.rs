@@ -0,0 +1,86 @@
+//! Accounts outside Turkey that cross-border settlements are paid out to, for members settled
+//! with `SettlementEarningsDestination::CrossBorder`.
+
+use serde::{Deserialize, Serialize};
+use serde_enum_str::{Deserialize_enum_str, Serialize_enum_str};
+use typed_builder::TypedBuilder;
+
+use crate::id::{MemberId, PayoutAccountId};
+use crate::request::Currency;
+
+/// Provider that holds a payout account
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PayoutAccountType {
+    Wise,
+    #[serde(other)]
+    Unhandled(String),
+}
+
+/// Whose earnings are paid out to a payout account
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AccountOwner {
+    Merchant,
+    SubMerchantMember,
+    #[serde(other)]
+    Unhandled(String),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PayoutAccount {
+    pub id: PayoutAccountId,
+    #[serde(rename = "type")]
+    pub account_type: PayoutAccountType,
+    /// ID of the account at the provider
+    pub external_account_id: String,
+    pub currency: Currency,
+    pub account_owner: AccountOwner,
+    /// Set when `account_owner` is [`AccountOwner::SubMerchantMember`]
+    pub sub_merchant_member_id: Option<MemberId>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default))]
+pub struct CreatePayoutAccountRequest {
+    #[serde(rename = "type")]
+    #[builder(default = PayoutAccountType::Wise)]
+    pub account_type: PayoutAccountType,
+    #[builder(!default)]
+    pub external_account_id: String,
+    #[builder(!default)]
+    pub currency: Currency,
+    #[builder(!default)]
+    pub account_owner: AccountOwner,
+    pub sub_merchant_member_id: Option<MemberId>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePayoutAccountResponse {
+    pub id: PayoutAccountId,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePayoutAccountRequest {
+    #[serde(rename = "type")]
+    #[builder(default = PayoutAccountType::Wise)]
+    pub account_type: PayoutAccountType,
+    pub external_account_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default))]
+pub struct SearchPayoutAccountsRequest {
+    #[builder(default = 0)]
+    pub page: u64,
+    #[builder(default = 25)]
+    pub size: u64,
+    pub currency: Option<Currency>,
+    pub account_owner: Option<AccountOwner>,
+    pub sub_merchant_member_id: Option<MemberId>,
+}
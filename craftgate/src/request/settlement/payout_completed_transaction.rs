@@ -0,0 +1,57 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_enum_str::{Deserialize_enum_str, Serialize_enum_str};
+use time::OffsetDateTime;
+use typed_builder::TypedBuilder;
+
+use crate::id::{PaymentId, PaymentTransactionId, PayoutId};
+use crate::request::{Currency, PayoutReturnStatus};
+
+/// Receiver of a payout
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MerchantType {
+    Merchant,
+    SubMerchantMember,
+    #[serde(other)]
+    Unhandled(String),
+}
+
+/// Share of a payment transaction in a completed payout
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PayoutCompletedTransaction {
+    pub payout_id: PayoutId,
+    /// Missing for payouts that are not made for a payment, e.g. wallet withdrawals
+    pub payment_id: Option<PaymentId>,
+    pub payment_transaction_id: Option<PaymentTransactionId>,
+    pub payout_amount: Decimal,
+    #[serde(with = "crate::request::date::craftgate")]
+    pub payout_date: OffsetDateTime,
+    pub currency: Currency,
+    pub merchant_type: MerchantType,
+    pub payout_return_status: Option<PayoutReturnStatus>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default))]
+pub struct SearchPayoutCompletedTransactionsRequest {
+    #[builder(default = 0)]
+    pub page: u64,
+    #[builder(default = 25)]
+    pub size: u64,
+    pub settlement_file_id: Option<u64>,
+    #[serde(with = "crate::request::date::craftgate::option")]
+    #[serde(default)]
+    pub start_date: Option<OffsetDateTime>,
+    #[serde(with = "crate::request::date::craftgate::option")]
+    #[serde(default)]
+    pub end_date: Option<OffsetDateTime>,
+}
+
+impl Default for SearchPayoutCompletedTransactionsRequest {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
@@ -0,0 +1,55 @@
+use std::fmt;
+
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+/// Requests that can be checked before they are sent, so invalid requests fail without a round
+/// trip to Craftgate.
+pub trait Validate {
+    /// Returns every violation found in the request
+    fn validate(&self) -> Result<(), Vec<Violation>>;
+}
+
+/// A field of a request that Craftgate would reject
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Path of the field in the request, e.g. `items[0].price`
+    pub field: String,
+    pub kind: ViolationKind,
+}
+
+/// Rule broken by a field, checked by the client. These are not Craftgate error codes, a request
+/// Craftgate rejects fails with [`CraftgateError::ValidationError`](crate::client::CraftgateError)
+/// instead.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ViolationKind {
+    #[error("must be greater than zero")]
+    NotPositive,
+    #[error("must not have more than {scale} decimal places")]
+    InvalidScale { scale: u32 },
+    #[error("must not be lower than price")]
+    PaidPriceLowerThanPrice,
+    #[error("sum of item prices {items} must be equal to price {price}")]
+    ItemSumMismatch { price: Decimal, items: Decimal },
+    #[error("must not be empty")]
+    Empty,
+    #[error("must be between {min} and {max}")]
+    OutOfRange { min: u64, max: u64 },
+    #[error("must be an absolute http or https URL")]
+    InvalidUrl,
+}
+
+impl Violation {
+    pub fn new(field: impl Into<String>, kind: ViolationKind) -> Self {
+        Self {
+            field: field.into(),
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.field, self.kind)
+    }
+}
@@ -0,0 +1,91 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use typed_builder::TypedBuilder;
+
+use crate::id::{CardToken, CardUserKey, MemberId, PaymentId};
+use crate::request::money::Money;
+use crate::request::payment::{Card, PaymentStatus, PaymentType};
+use crate::request::wallet::WalletTransaction;
+use crate::request::Currency;
+
+/// Top-up of the wallet of `buyer_member_id`, collected from a card directly
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default))]
+pub struct CreateDepositPaymentRequest {
+    #[builder(!default)]
+    pub price: Decimal,
+    #[builder(!default)]
+    pub buyer_member_id: MemberId,
+    pub currency: Currency,
+    pub conversation_id: Option<String>,
+    pub payment_channel: Option<String>,
+    /// Alias of the POS the deposit should be collected through
+    pub pos_alias: Option<String>,
+    #[builder(!default)]
+    pub card: Card,
+}
+
+/// Top-up collected from a card with 3D Secure, see
+/// [`InitThreeDSPaymentRequest`](crate::request::payment::three_ds_payment::InitThreeDSPaymentRequest)
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+pub struct InitThreeDSDepositPaymentRequest {
+    #[serde(flatten)]
+    pub deposit: CreateDepositPaymentRequest,
+    pub callback_url: String,
+}
+
+/// Top-up collected on the Craftgate hosted checkout form
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default))]
+pub struct InitCheckoutDepositPaymentRequest {
+    #[builder(!default)]
+    pub price: Decimal,
+    #[builder(!default)]
+    pub buyer_member_id: MemberId,
+    pub currency: Currency,
+    pub conversation_id: Option<String>,
+    pub payment_channel: Option<String>,
+    #[builder(!default)]
+    pub callback_url: String,
+    /// Lifetime of the checkout token in seconds
+    pub ttl: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositPayment {
+    pub id: PaymentId,
+    #[serde(with = "crate::request::date::craftgate")]
+    pub created_date: OffsetDateTime,
+    pub price: Decimal,
+    pub currency: Currency,
+    pub buyer_member_id: MemberId,
+    pub conversation_id: Option<String>,
+    pub bank_commission_rate: Option<Decimal>,
+    pub bank_commission_rate_amount: Option<Decimal>,
+    pub auth_code: Option<String>,
+    pub host_reference: Option<String>,
+    pub trans_id: Option<String>,
+    pub order_id: Option<String>,
+    pub payment_type: PaymentType,
+    pub payment_status: PaymentStatus,
+    pub card_user_key: Option<CardUserKey>,
+    pub card_token: Option<CardToken>,
+    /// Credit of the deposit to the buyer's wallet, missing unless the deposit succeeded. Craftgate
+    /// does not send the new balance, it is returned by
+    /// [`retrieve_member_wallet`](crate::client::CraftgateClient::retrieve_member_wallet).
+    pub wallet_transaction: Option<WalletTransaction>,
+}
+
+impl DepositPayment {
+    pub fn price(&self) -> Money {
+        Money {
+            amount: self.price,
+            currency: self.currency.clone(),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::id::MemberId;
+use crate::request::wallet::WalletTransaction;
+use crate::request::Currency;
+
+/// Credits a member wallet with money received by bank transfer outside Craftgate, e.g. for
+/// sub-merchants settled with `SettlementEarningsDestination::Wallet`. Craftgate records it as a
+/// [`PaymentType::BankTransfer`](crate::request::payment::PaymentType::BankTransfer).
+#[derive(Serialize, Deserialize, Debug, Clone, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default))]
+pub struct CreateFundTransferDepositPaymentRequest {
+    #[builder(!default)]
+    pub price: Decimal,
+    #[builder(!default)]
+    pub buyer_member_id: MemberId,
+    pub conversation_id: Option<String>,
+    pub client_ip: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FundTransferDepositPayment {
+    pub price: Decimal,
+    pub currency: Currency,
+    pub conversation_id: Option<String>,
+    pub buyer_member_id: MemberId,
+    /// Credit of the transfer to the member's wallet
+    pub wallet_transaction: WalletTransaction,
+}
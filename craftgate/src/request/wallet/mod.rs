@@ -0,0 +1,63 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_enum_str::{Deserialize_enum_str, Serialize_enum_str};
+use time::OffsetDateTime;
+
+use crate::id::{MemberId, WalletId, WalletTransactionId};
+use crate::request::Currency;
+
+pub mod deposit_payment;
+pub mod fund_transfer_deposit;
+
+/// Wallet of a buyer or sub-merchant member
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Wallet {
+    pub id: WalletId,
+    #[serde(with = "crate::request::date::craftgate")]
+    pub created_date: OffsetDateTime,
+    #[serde(with = "crate::request::date::craftgate::option")]
+    #[serde(default)]
+    pub updated_date: Option<OffsetDateTime>,
+    /// Balance of the wallet
+    pub amount: Decimal,
+    /// Part of the balance that can be withdrawn
+    pub withdrawal_amount: Decimal,
+    pub currency: Currency,
+    pub member_id: MemberId,
+}
+
+/// Reason of a change in a wallet balance
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WalletTransactionType {
+    /// Deposit from a card
+    DepositFromCard,
+    /// Deposit from an alternative payment method
+    DepositFromApm,
+    /// Deposit from a bank transfer
+    DepositFromFundTransfer,
+    /// Payment collected from the wallet
+    PaymentRedeem,
+    /// Refund of a payment to the wallet
+    RefundTxToWallet,
+    /// Earnings of a sub-merchant settled to the wallet
+    SettlementEarnings,
+    /// Withdrawal from the wallet
+    Withdraw,
+    #[serde(other)]
+    Unhandled(String),
+}
+
+/// A single change in a wallet balance
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletTransaction {
+    pub id: WalletTransactionId,
+    #[serde(with = "crate::request::date::craftgate")]
+    pub created_date: OffsetDateTime,
+    pub wallet_transaction_type: WalletTransactionType,
+    /// Amount added to the wallet, negative if deducted
+    pub amount: Decimal,
+    pub wallet_id: WalletId,
+}
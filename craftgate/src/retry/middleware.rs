@@ -0,0 +1,259 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Request, Response, StatusCode};
+use reqwest_middleware::{Middleware, Next};
+use serde::Deserialize;
+use task_local_extensions::Extensions;
+use thiserror::Error;
+use time::format_description::well_known::Rfc2822;
+use time::OffsetDateTime;
+
+use crate::api_error::{ErrorGroup, ErrorResponse};
+use crate::middleware::buffer;
+use crate::transport::Operation;
+
+use super::RetryPolicy;
+
+#[derive(Error, Debug)]
+pub enum RetryError {
+    #[error("Request can not be retried since its body is not cloneable")]
+    UncloneableRequest,
+}
+
+/// Retries requests according to a [`RetryPolicy`] and the [`Operation`] attached to them.
+///
+/// Safe operations are retried on connection failures, timeouts, `429` and `5xx` responses and on
+/// Craftgate errors in the `COMMUNICATION_OR_SYSTEM_ERROR`, `REQUEST_TIMEOUT` and `NO_RESPONSE`
+/// groups. Mutations are retried only when the connection could not be established or on `429`
+/// responses. `429` responses are not retried if their `Retry-After` exceeds
+/// [`RetryPolicy::max_retry_after`].
+pub struct RetryMiddleware {
+    policy: RetryPolicy,
+}
+
+impl RetryMiddleware {
+    pub fn new_with_policy(policy: RetryPolicy) -> Self {
+        Self { policy }
+    }
+}
+
+enum Outcome {
+    Done(reqwest_middleware::Result<Response>),
+    Retry {
+        result: reqwest_middleware::Result<Response>,
+        retry_after: Option<Duration>,
+    },
+}
+
+#[async_trait]
+impl Middleware for RetryMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        self.execute_with_retry(req, next, extensions).await
+    }
+}
+
+impl RetryMiddleware {
+    async fn execute_with_retry<'a>(
+        &'a self,
+        req: Request,
+        next: Next<'a>,
+        extensions: &'a mut Extensions,
+    ) -> reqwest_middleware::Result<Response> {
+        let operation = extensions
+            .get::<Operation>()
+            .copied()
+            .unwrap_or(Operation::Mutation);
+
+        let mut n_past_retries = 0;
+        loop {
+            let duplicate_request = req.try_clone().ok_or_else(|| {
+                reqwest_middleware::Error::middleware(RetryError::UncloneableRequest)
+            })?;
+
+            let result = next.clone().run(duplicate_request, extensions).await;
+
+            let (result, retry_after) = match classify(operation, result).await {
+                Outcome::Done(result) => return result,
+                Outcome::Retry {
+                    result,
+                    retry_after,
+                } => (result, retry_after),
+            };
+
+            if n_past_retries >= self.policy.max_retries {
+                return result;
+            }
+
+            let delay = match retry_after {
+                Some(retry_after) if retry_after > self.policy.max_retry_after => return result,
+                Some(retry_after) => retry_after,
+                None => self.policy.backoff(n_past_retries),
+            };
+
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                "Retry attempt #{}. Sleeping {:?} before the next attempt",
+                n_past_retries,
+                delay
+            );
+            tokio::time::sleep(delay).await;
+
+            n_past_retries += 1;
+        }
+    }
+}
+
+async fn classify(operation: Operation, result: reqwest_middleware::Result<Response>) -> Outcome {
+    let resp = match result {
+        Ok(resp) => resp,
+        Err(reqwest_middleware::Error::Reqwest(e))
+            if e.is_connect() || (operation == Operation::Safe && e.is_timeout()) =>
+        {
+            return Outcome::Retry {
+                result: Err(e.into()),
+                retry_after: None,
+            }
+        }
+        Err(e) => return Outcome::Done(Err(e)),
+    };
+
+    let status = resp.status();
+    let retry_after = parse_retry_after(&resp);
+
+    // A rate limited request was rejected before being processed, so even mutations can be sent
+    // again.
+    if operation == Operation::Mutation {
+        return match status {
+            StatusCode::TOO_MANY_REQUESTS => Outcome::Retry {
+                result: Ok(resp),
+                retry_after,
+            },
+            _ => Outcome::Done(Ok(resp)),
+        };
+    }
+
+    if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+        return Outcome::Retry {
+            result: Ok(resp),
+            retry_after,
+        };
+    }
+
+    // The error group is only available in the body, so it has to be buffered and put back into
+    // a new response for the caller.
+    let (resp, body) = match buffer(resp).await {
+        Ok(buffered) => buffered,
+        Err(e) => return Outcome::Done(Err(e.into())),
+    };
+
+    let resp = Ok(resp);
+    if is_transient_error_body(&body) {
+        Outcome::Retry {
+            result: resp,
+            retry_after,
+        }
+    } else {
+        Outcome::Done(resp)
+    }
+}
+
+#[derive(Deserialize)]
+struct ErrorEnvelope {
+    errors: Option<ErrorResponse>,
+}
+
+fn is_transient_error_body(body: &[u8]) -> bool {
+    let Ok(ErrorEnvelope {
+        errors: Some(ErrorResponse {
+            group: Some(group), ..
+        }),
+    }) = serde_json::from_slice::<ErrorEnvelope>(body)
+    else {
+        return false;
+    };
+
+    matches!(
+        group,
+        ErrorGroup::CommunicationOrSystemError
+            | ErrorGroup::RequestTimeout
+            | ErrorGroup::NoResponse
+    )
+}
+
+/// Parses a `Retry-After` header given either in seconds or as an HTTP date.
+fn parse_retry_after(resp: &Response) -> Option<Duration> {
+    let value = resp.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = OffsetDateTime::parse(value, &Rfc2822).ok()?;
+    let wait = date - OffsetDateTime::now_utc();
+
+    Some(wait.try_into().unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::Response;
+
+    use crate::transport::Operation;
+
+    use super::{classify, is_transient_error_body, parse_retry_after, Outcome};
+
+    fn response_with_retry_after(value: &str) -> Response {
+        http::Response::builder()
+            .status(429)
+            .header("retry-after", value)
+            .body("")
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn parses_retry_after_seconds() {
+        let resp = response_with_retry_after("7");
+
+        assert_eq!(parse_retry_after(&resp), Some(Duration::from_secs(7)));
+    }
+
+    #[test]
+    fn parses_past_retry_after_date_as_zero() {
+        let resp = response_with_retry_after("Wed, 21 Oct 2015 07:28:00 GMT");
+
+        assert_eq!(parse_retry_after(&resp), Some(Duration::ZERO));
+    }
+
+    #[tokio::test]
+    async fn retries_rate_limited_mutations_after_retry_after() {
+        let resp = response_with_retry_after("7");
+
+        let outcome = classify(Operation::Mutation, Ok(resp)).await;
+        assert!(matches!(
+            outcome,
+            Outcome::Retry { retry_after: Some(wait), .. } if wait == Duration::from_secs(7)
+        ));
+    }
+
+    #[test]
+    fn detects_transient_error_groups() {
+        let body = br#"{"errors": {"errorCode": "10000", "errorDescription": "No response", "errorGroup": "NO_RESPONSE"}}"#;
+        assert!(is_transient_error_body(body));
+
+        let body = br#"{"errors": {"errorCode": "10051", "errorDescription": "Insufficient funds", "errorGroup": "NOT_SUFFICIENT_FUNDS"}}"#;
+        assert!(!is_transient_error_body(body));
+
+        let body = br#"{"data": {"id": 1}}"#;
+        assert!(!is_transient_error_body(body));
+    }
+}
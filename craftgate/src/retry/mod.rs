@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use rand::Rng;
+use typed_builder::TypedBuilder;
+
+#[cfg(feature = "retry")]
+mod middleware;
+
+#[cfg(feature = "retry")]
+pub use middleware::{RetryError, RetryMiddleware};
+
+/// How failed requests are retried. Only used with the `retry` feature, without it every request
+/// is sent once.
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt
+    #[builder(default = 3)]
+    pub max_retries: u32,
+    /// Backoff before the first retry, doubled on every following retry
+    #[builder(default = Duration::from_millis(250))]
+    pub min_backoff: Duration,
+    /// Upper bound of the exponential backoff
+    #[builder(default = Duration::from_secs(10))]
+    pub max_backoff: Duration,
+    /// Upper bound for a wait requested by a `Retry-After` header. Responses asking for a longer
+    /// wait are returned as they are.
+    #[builder(default = Duration::from_secs(30))]
+    pub max_retry_after: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that never retries
+    pub fn none() -> Self {
+        Self::builder().max_retries(0).build()
+    }
+
+    #[cfg_attr(not(feature = "retry"), allow(dead_code))]
+    fn backoff(&self, n_past_retries: u32) -> Duration {
+        let backoff = self
+            .min_backoff
+            .saturating_mul(2u32.saturating_pow(n_past_retries))
+            .min(self.max_backoff);
+
+        // Equal jitter, keeps at least half of the backoff.
+        let half = backoff / 2;
+        let jitter = rand::thread_rng().gen_range(0..=half.as_millis() as u64);
+
+        half + Duration::from_millis(jitter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::RetryPolicy;
+
+    #[test]
+    fn backoff_is_capped() {
+        let policy = RetryPolicy::builder()
+            .min_backoff(Duration::from_secs(1))
+            .max_backoff(Duration::from_secs(4))
+            .build();
+
+        for n in 0..10 {
+            let backoff = policy.backoff(n);
+            assert!(backoff <= Duration::from_secs(4));
+            assert!(backoff >= Duration::from_millis(500));
+        }
+    }
+}
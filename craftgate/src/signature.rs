@@ -0,0 +1,113 @@
+//! Request signing, independent of the HTTP stack. Requests sent without the client can be signed
+//! with [`Credentials::sign`], or with [`signature`] and [`insert_signature_headers`].
+
+use std::str::FromStr;
+
+use bytes::{BufMut, BytesMut};
+use data_encoding::BASE64;
+use hmac_sha256::Hash;
+use http::header::{HeaderMap, HeaderName, InvalidHeaderValue};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use secrecy::{ExposeSecret, SecretString};
+use thiserror::Error;
+
+use crate::transport::HttpRequest;
+
+pub(crate) const X_API_KEY: HeaderName = HeaderName::from_static("x-api-key");
+pub(crate) const X_RND_KEY: HeaderName = HeaderName::from_static("x-rnd-key");
+pub(crate) const X_AUTH_VERSION: HeaderName = HeaderName::from_static("x-auth-version");
+pub(crate) const X_SIGNATURE: HeaderName = HeaderName::from_static("x-signature");
+
+/// API keys requests are signed with
+#[derive(Clone)]
+pub struct Credentials {
+    access_key: SecretString,
+    secret_key: SecretString,
+}
+
+impl Credentials {
+    pub fn new(access_key: &str, secret_key: &str) -> Self {
+        Self {
+            access_key: SecretString::from_str(access_key).expect("infallible"),
+            secret_key: SecretString::from_str(secret_key).expect("infallible"),
+        }
+    }
+
+    /// Adds the authentication headers to `request`. Has to be called after the url and body of
+    /// the request are final.
+    pub fn sign(&self, request: &mut HttpRequest) -> Result<(), SignatureCalculationError> {
+        let random_string = random_string();
+        let signature = signature(
+            request.url.as_str(),
+            self.access_key.expose_secret(),
+            self.secret_key.expose_secret(),
+            &random_string,
+            request.body.as_deref(),
+        );
+
+        insert_signature_headers(
+            &mut request.headers,
+            self.access_key.expose_secret(),
+            &random_string,
+            &signature,
+        )
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum SignatureCalculationError {
+    #[error("Body is incompatible for safe consumption")]
+    IncompatibleBody,
+    #[error("Invalid header value {0}")]
+    InvalidHeaderValue(#[from] InvalidHeaderValue),
+}
+
+/// Signature Craftgate expects in the `x-signature` header: the base64 encoded SHA-256 hash of the
+/// request url, the keys, the random string and the request body.
+pub fn signature(
+    url: &str,
+    access_key: &str,
+    secret_key: &str,
+    random_string: &str,
+    body: Option<&[u8]>,
+) -> String {
+    let mut payload = BytesMut::new();
+
+    payload.put_slice(url.as_bytes());
+    payload.put_slice(access_key.as_bytes());
+    payload.put_slice(secret_key.as_bytes());
+
+    payload.put_slice(random_string.as_bytes());
+
+    if let Some(body) = body {
+        payload.put_slice(body);
+    }
+
+    let hash = Hash::hash(payload.as_ref());
+
+    BASE64.encode(&hash)
+}
+
+/// Inserts the authentication headers for a request with the given `signature`
+pub fn insert_signature_headers(
+    headers: &mut HeaderMap,
+    access_key: &str,
+    random_string: &str,
+    signature: &str,
+) -> Result<(), SignatureCalculationError> {
+    headers.insert(X_API_KEY, access_key.parse()?);
+    headers.insert(X_RND_KEY, random_string.parse()?);
+    headers.insert(X_AUTH_VERSION, "1".parse()?);
+    headers.insert(X_SIGNATURE, signature.parse()?);
+
+    Ok(())
+}
+
+pub(crate) fn random_string() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(64)
+        .map(char::from)
+        .collect()
+}
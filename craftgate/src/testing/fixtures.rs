@@ -0,0 +1,458 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use http::header::CONTENT_TYPE;
+use reqwest::{Request, Response, Url};
+use reqwest_middleware::{Middleware, Next};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use task_local_extensions::Extensions;
+use thiserror::Error;
+
+use crate::middleware::buffer;
+
+/// Value recorded in place of redacted fields
+pub const REDACTED: &str = "REDACTED";
+
+/// Request and response fields that are replaced with [`REDACTED`] before a fixture is written.
+/// Headers are never recorded, so the API key, random key and signature are not stored either.
+const REDACTED_FIELDS: &[&str] = &[
+    "cardNumber",
+    "cvc",
+    "expireYear",
+    "expireMonth",
+    "cardHolderName",
+    "bankCardHolderName",
+    "cardUserKey",
+    "cardToken",
+    "email",
+    "phoneNumber",
+    "gsmNumber",
+    "masterpassGsmNumber",
+    "msisdn",
+    "address",
+    "iban",
+    "identityNumber",
+    "taxNumber",
+    "contactName",
+    "contactSurname",
+    "clientIp",
+];
+
+/// Fields holding the name of the person or company in the requests and responses of the member
+/// endpoints, redacted there on top of [`REDACTED_FIELDS`]. Elsewhere `name` is the name of an
+/// item, POS or bank and is kept.
+const MEMBER_REDACTED_FIELDS: &[&str] = &["name"];
+
+/// Path of the member endpoints
+const MEMBERS_PATH: &str = "/onboarding/v1/members";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FixtureRequest {
+    pub method: String,
+    pub path: String,
+    pub query: Option<String>,
+    pub body: Option<Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FixtureResponse {
+    pub status: u16,
+    /// Set for CSV responses, e.g. transaction reports. Their `body` is the redacted text of the
+    /// response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    pub body: Option<Value>,
+}
+
+/// A request and the response Craftgate sent for it
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Interaction {
+    pub request: FixtureRequest,
+    pub response: FixtureResponse,
+}
+
+#[derive(Error, Debug)]
+pub enum FixtureError {
+    #[error("Fixture io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Fixture serde error: {0}")]
+    Serde(#[from] serde_json::Error),
+    #[error("Fixture csv error: {0}")]
+    Csv(#[from] csv::Error),
+    /// Only JSON and CSV responses can be redacted, other responses are not recorded
+    #[error("Cannot record response with content type {0:?}")]
+    UnsupportedContentType(Option<String>),
+    #[error("No recorded interaction left for {method} {path}")]
+    Exhausted { method: String, path: String },
+    #[error("Expected request {expected_method} {expected_path}, got {method} {path}")]
+    Mismatch {
+        expected_method: String,
+        expected_path: String,
+        method: String,
+        path: String,
+    },
+}
+
+enum Mode {
+    Record(Mutex<Vec<Interaction>>),
+    Replay(Mutex<VecDeque<Interaction>>),
+}
+
+/// Records requests sent to Craftgate and their responses to a JSON fixture file, or serves
+/// previously recorded responses without sending anything.
+///
+/// A fixture file holds the interactions of one test in the order they happened. On replay the
+/// requests must arrive in the same order, with the same method and path. Request bodies are not
+/// compared, since they often contain generated ids.
+///
+/// Add the middleware with [`ClientOptions::middleware`](crate::client::ClientOptions), it runs
+/// after the request is signed.
+pub struct FixtureMiddleware {
+    path: PathBuf,
+    mode: Mode,
+}
+
+impl FixtureMiddleware {
+    /// Sends requests to Craftgate and writes the redacted interactions to `path`, replacing the
+    /// file if it exists.
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            mode: Mode::Record(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Serves the interactions recorded in `path`
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self, FixtureError> {
+        let path = path.into();
+        let interactions: VecDeque<Interaction> = serde_json::from_slice(&fs::read(&path)?)?;
+
+        Ok(Self {
+            path,
+            mode: Mode::Replay(Mutex::new(interactions)),
+        })
+    }
+
+    async fn record_interaction(
+        &self,
+        interactions: &Mutex<Vec<Interaction>>,
+        request: FixtureRequest,
+        resp: Response,
+    ) -> Result<Response, reqwest_middleware::Error> {
+        let (resp, body) = buffer(resp).await?;
+        let content_type = resp
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(ToOwned::to_owned);
+
+        let (body, content_type) = match serde_json::from_slice::<Value>(&body) {
+            Ok(mut body) => {
+                if is_member_path(&request.path) {
+                    redact_member(&mut body);
+                } else {
+                    redact(&mut body);
+                }
+                (Some(body), None)
+            }
+            Err(_) if body.is_empty() => (None, None),
+            Err(_) if content_type.as_deref().is_some_and(is_csv) => {
+                let body = redact_csv(&body).map_err(reqwest_middleware::Error::middleware)?;
+                (Some(Value::String(body)), content_type)
+            }
+            Err(_) => {
+                return Err(reqwest_middleware::Error::middleware(
+                    FixtureError::UnsupportedContentType(content_type),
+                ))
+            }
+        };
+
+        let interaction = Interaction {
+            request,
+            response: FixtureResponse {
+                status: resp.status().as_u16(),
+                content_type,
+                body,
+            },
+        };
+
+        let mut interactions = interactions.lock().expect("fixture interactions");
+        interactions.push(interaction);
+        self.write(&interactions)
+            .map_err(reqwest_middleware::Error::middleware)?;
+
+        Ok(resp)
+    }
+
+    fn write(&self, interactions: &[Interaction]) -> Result<(), FixtureError> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(&self.path, serde_json::to_vec_pretty(interactions)?)?;
+
+        Ok(())
+    }
+
+    fn replay_interaction(
+        interactions: &Mutex<VecDeque<Interaction>>,
+        request: FixtureRequest,
+    ) -> Result<Response, FixtureError> {
+        let interaction = interactions
+            .lock()
+            .expect("fixture interactions")
+            .pop_front()
+            .ok_or_else(|| FixtureError::Exhausted {
+                method: request.method.clone(),
+                path: request.path.clone(),
+            })?;
+
+        if interaction.request.method != request.method || interaction.request.path != request.path
+        {
+            return Err(FixtureError::Mismatch {
+                expected_method: interaction.request.method,
+                expected_path: interaction.request.path,
+                method: request.method,
+                path: request.path,
+            });
+        }
+
+        let response = interaction.response;
+        let body = match (response.body, &response.content_type) {
+            (Some(Value::String(text)), Some(_)) => text,
+            (Some(body), _) => body.to_string(),
+            (None, _) => String::new(),
+        };
+        let resp = http::Response::builder()
+            .status(response.status)
+            .header(
+                CONTENT_TYPE,
+                response
+                    .content_type
+                    .as_deref()
+                    .unwrap_or("application/json"),
+            )
+            .body(body)
+            .expect("valid response");
+
+        Ok(Response::from(resp))
+    }
+}
+
+#[async_trait]
+impl Middleware for FixtureMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let request = fixture_request(&req);
+
+        match &self.mode {
+            Mode::Record(interactions) => {
+                let resp = next.run(req, extensions).await?;
+                self.record_interaction(interactions, request, resp).await
+            }
+            Mode::Replay(interactions) => Self::replay_interaction(interactions, request)
+                .map_err(reqwest_middleware::Error::middleware),
+        }
+    }
+}
+
+fn fixture_request(req: &Request) -> FixtureRequest {
+    let mut body: Option<Value> = req
+        .body()
+        .and_then(|body| body.as_bytes())
+        .and_then(|body| serde_json::from_slice(body).ok());
+    if let Some(body) = body.as_mut() {
+        if is_member_path(req.url().path()) {
+            redact_member(body);
+        } else {
+            redact(body);
+        }
+    }
+
+    FixtureRequest {
+        method: req.method().to_string(),
+        path: req.url().path().to_owned(),
+        query: redact_query(req.url()),
+        body,
+    }
+}
+
+/// Replaces card data and personal data in `value` and all nested objects with [`REDACTED`]
+pub fn redact(value: &mut Value) {
+    redact_fields(value, &[]);
+}
+
+/// Like [`redact`], also replacing the name of the member in a member request or response
+pub fn redact_member(value: &mut Value) {
+    redact_fields(value, MEMBER_REDACTED_FIELDS);
+}
+
+fn redact_fields(value: &mut Value, extra: &[&str]) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let key = key.as_str();
+                if (REDACTED_FIELDS.contains(&key) || extra.contains(&key)) && value.is_string() {
+                    *value = Value::String(REDACTED.to_owned());
+                } else {
+                    redact_fields(value, extra);
+                }
+            }
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| redact_fields(value, extra)),
+        _ => {}
+    }
+}
+
+fn is_member_path(path: &str) -> bool {
+    path.starts_with(MEMBERS_PATH)
+}
+
+fn is_csv(content_type: &str) -> bool {
+    content_type
+        .split(';')
+        .next()
+        .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("text/csv"))
+}
+
+/// Replaces the non-empty values of the columns named like a redacted field with [`REDACTED`]
+fn redact_csv(body: &[u8]) -> Result<String, FixtureError> {
+    let mut reader = csv::Reader::from_reader(body);
+    let headers = reader.headers()?.clone();
+    let redacted: Vec<bool> = headers
+        .iter()
+        .map(|header| REDACTED_FIELDS.contains(&header))
+        .collect();
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(&headers)?;
+    for record in reader.records() {
+        let record = record?;
+        writer.write_record(record.iter().zip(&redacted).map(|(value, &redacted)| {
+            if redacted && !value.is_empty() {
+                REDACTED
+            } else {
+                value
+            }
+        }))?;
+    }
+    let body = writer
+        .into_inner()
+        .map_err(|e| FixtureError::Io(e.into_error()))?;
+
+    Ok(String::from_utf8(body).expect("CSV records are UTF-8"))
+}
+
+fn redact_query(url: &Url) -> Option<String> {
+    url.query()?;
+
+    let member = is_member_path(url.path());
+    let mut redacted = Url::parse("http://localhost").expect("valid url");
+    {
+        let mut pairs = redacted.query_pairs_mut();
+        for (key, value) in url.query_pairs() {
+            if REDACTED_FIELDS.contains(&key.as_ref())
+                || (member && MEMBER_REDACTED_FIELDS.contains(&key.as_ref()))
+            {
+                pairs.append_pair(&key, REDACTED);
+            } else {
+                pairs.append_pair(&key, &value);
+            }
+        }
+    }
+
+    redacted.query().map(ToOwned::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Url;
+    use serde_json::json;
+
+    use super::{redact, redact_csv, redact_member, redact_query};
+
+    #[test]
+    fn redacts_nested_fields() {
+        let mut value = json!({
+            "email": "haluk.demir@example.com",
+            "name": "Haluk Demir",
+            "memberExternalId": "d8fa867b-000b-4b96-ad3c-43ea22e65e3f",
+            "card": {
+                "cardNumber": "5258640000000001",
+                "cvc": "000"
+            },
+            "items": [{ "iban": "TR930006701000000001111111" }]
+        });
+
+        redact(&mut value);
+
+        assert_eq!(
+            value,
+            json!({
+                "email": "REDACTED",
+                "name": "Haluk Demir",
+                "memberExternalId": "d8fa867b-000b-4b96-ad3c-43ea22e65e3f",
+                "card": {
+                    "cardNumber": "REDACTED",
+                    "cvc": "REDACTED"
+                },
+                "items": [{ "iban": "REDACTED" }]
+            })
+        );
+    }
+
+    #[test]
+    fn redacts_member_names_only() {
+        let mut member = json!({ "name": "Haluk Demir", "contactName": "Haluk" });
+        let mut payment =
+            json!({ "pos": { "name": "Sandbox POS" }, "items": [{ "name": "Item 1" }] });
+
+        redact_member(&mut member);
+        redact(&mut payment);
+
+        assert_eq!(
+            member,
+            json!({ "name": "REDACTED", "contactName": "REDACTED" })
+        );
+        assert_eq!(
+            payment,
+            json!({ "pos": { "name": "Sandbox POS" }, "items": [{ "name": "Item 1" }] })
+        );
+    }
+
+    #[test]
+    fn redacts_query_parameters() {
+        let url = Url::parse(
+            "https://sandbox-api.craftgate.io/onboarding/v1/members?page=0&email=haluk%40example.com",
+        )
+        .unwrap();
+
+        assert_eq!(redact_query(&url).as_deref(), Some("page=0&email=REDACTED"));
+
+        let url =
+            Url::parse("https://sandbox-api.craftgate.io/onboarding/v1/members?name=Haluk+Demir")
+                .unwrap();
+
+        assert_eq!(redact_query(&url).as_deref(), Some("name=REDACTED"));
+    }
+
+    #[test]
+    fn redacts_csv_columns() {
+        let csv = "paymentId,cardHolderName,paidPrice\n1045213,\"Demir, Haluk\",100.00\n1045301,,150.00\n";
+
+        assert_eq!(
+            redact_csv(csv.as_bytes()).unwrap(),
+            "paymentId,cardHolderName,paidPrice\n1045213,REDACTED,100.00\n1045301,,150.00\n"
+        );
+    }
+}
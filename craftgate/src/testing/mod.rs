@@ -0,0 +1,18 @@
+//! Utilities for testing code that uses the Craftgate client without network access or sandbox
+//! credentials. Available with the `test-util` feature, the mock server also needs the
+//! `onboarding` and `payment` features.
+
+mod fixtures;
+#[cfg(all(feature = "onboarding", feature = "payment"))]
+mod server;
+
+pub use fixtures::{
+    redact, redact_member, FixtureError, FixtureMiddleware, FixtureRequest, FixtureResponse,
+    Interaction, REDACTED,
+};
+
+#[cfg(all(feature = "onboarding", feature = "payment"))]
+pub use server::{
+    MockCraftgateServer, MockError, RecordedRequest, INVALID_REQUEST_ERROR_CODE,
+    NOT_FOUND_ERROR_CODE, UNAUTHORIZED_ERROR_CODE,
+};
@@ -0,0 +1,563 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use http::header::HOST;
+use http::request::Parts;
+use http::{HeaderMap, HeaderName, Method, StatusCode};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use reqwest::Url;
+use serde::Serialize;
+use serde_json::{json, Value};
+use time::{Duration, OffsetDateTime};
+use tokio::task::JoinHandle;
+
+use crate::api_error::ErrorGroup;
+use crate::checkout::PAYMENT_NOT_COMPLETED;
+use crate::client::{ClientOptions, CraftgateClient};
+use crate::id::{CheckoutToken, MemberId};
+use crate::request::onboarding::create_member::CreateMemberRequest;
+use crate::request::onboarding::update_member::UpdateMemberRequest;
+use crate::request::onboarding::Member;
+use crate::request::payment::checkout_payment::CheckoutPaymentInitiationRequest;
+use crate::request::{date, Status};
+use crate::signature::{signature, X_API_KEY, X_RND_KEY, X_SIGNATURE};
+
+/// Error code the mock server responds with when the request signature can not be verified
+pub const UNAUTHORIZED_ERROR_CODE: u32 = 1;
+/// Error code the mock server responds with when the requested record does not exist
+pub const NOT_FOUND_ERROR_CODE: u32 = 2;
+/// Error code the mock server responds with when the request body can not be parsed
+pub const INVALID_REQUEST_ERROR_CODE: u32 = 3;
+
+/// Checkout tokens expire after 30 minutes unless `ttl` is set in the request
+const DEFAULT_CHECKOUT_TTL: i64 = 30 * 60;
+
+/// Error response the mock server sends instead of handling a request, see
+/// [`MockCraftgateServer::fail_next`].
+#[derive(Debug, Clone)]
+pub struct MockError {
+    pub status: StatusCode,
+    pub code: u32,
+    pub description: String,
+    pub group: Option<ErrorGroup>,
+}
+
+impl MockError {
+    /// Validation error, Craftgate uses codes below `10000` for these
+    pub fn validation(code: u32, description: &str) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            code,
+            description: description.to_owned(),
+            group: None,
+        }
+    }
+
+    /// Payment error returned by the bank, Craftgate uses codes from `10000` for these
+    pub fn payment(code: u32, group: ErrorGroup, description: &str) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            code,
+            description: description.to_owned(),
+            group: Some(group),
+        }
+    }
+
+    pub fn with_status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+}
+
+/// Request received by the mock server
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: Method,
+    pub path: String,
+    pub query: Option<String>,
+    pub body: Option<Value>,
+}
+
+/// In-process HTTP server emulating the member, checkout payment and payment endpoints of the
+/// Craftgate API. Requests are only handled if they carry a valid `x-signature` header for the
+/// keys the server is started with.
+///
+/// The server must be started inside a Tokio runtime and is shut down when dropped.
+pub struct MockCraftgateServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    handle: JoinHandle<()>,
+}
+
+impl MockCraftgateServer {
+    pub fn start(api_key: &str, secret_key: &str) -> Self {
+        let state = Arc::new(Mutex::new(State::new(api_key, secret_key)));
+
+        let service_state = state.clone();
+        let make_service = make_service_fn(move |_| {
+            let state = service_state.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |req| handle(state.clone(), req))) }
+        });
+
+        let server = Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_service);
+        let addr = server.local_addr();
+        let handle = tokio::spawn(async move {
+            #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+            let result = server.await;
+            #[cfg(feature = "tracing")]
+            if let Err(e) = result {
+                tracing::error!(error = %e, "mock craftgate server failed");
+            }
+        });
+
+        Self {
+            addr,
+            state,
+            handle,
+        }
+    }
+
+    pub fn base_url(&self) -> Url {
+        Url::parse(&format!("http://{}", self.addr)).expect("valid url")
+    }
+
+    /// Client sending requests to this server, signed with the keys the server was started with
+    pub fn client(&self) -> CraftgateClient {
+        self.client_with_options(ClientOptions::default())
+    }
+
+    /// Same as [`MockCraftgateServer::client`], `base_url` of the options is overridden
+    pub fn client_with_options(&self, options: ClientOptions) -> CraftgateClient {
+        let state = self.state();
+
+        CraftgateClient::new_with_options(
+            &state.api_key,
+            &state.secret_key,
+            ClientOptions {
+                base_url: Some(self.base_url()),
+                ..options
+            },
+        )
+    }
+
+    /// Responds to the next request with the given method and path with `error`. Errors
+    /// registered for the same request are sent in order.
+    pub fn fail_next(&self, method: Method, path: &str, error: MockError) {
+        self.state().errors.push((method, path.to_owned(), error));
+    }
+
+    /// Adds a payment that can be retrieved and searched. `payment` must serialize to the JSON
+    /// Craftgate returns for a payment.
+    pub fn insert_payment<P: Serialize>(&self, payment: &P) {
+        let payment = serde_json::to_value(payment).expect("payment serializes to JSON");
+
+        self.state().payments.push(payment);
+    }
+
+    /// Completes the checkout payment with the given token, as if the buyer paid on the checkout
+    /// page. The payment is returned by the checkout inquiry and can be retrieved afterwards.
+    pub fn complete_checkout<P: Serialize>(&self, token: &CheckoutToken, payment: &P) {
+        let payment = serde_json::to_value(payment).expect("payment serializes to JSON");
+
+        let mut state = self.state();
+        let checkout = state
+            .checkouts
+            .iter_mut()
+            .find(|c| c.token == token.as_str())
+            .expect("checkout payment initialized");
+        checkout.payment = Some(payment.clone());
+        state.payments.push(payment);
+    }
+
+    /// Requests received so far, in order
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state().requests.clone()
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("mock server state")
+    }
+}
+
+impl Drop for MockCraftgateServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+async fn handle(
+    state: Arc<Mutex<State>>,
+    req: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let (parts, body) = req.into_parts();
+    let body = hyper::body::to_bytes(body).await.unwrap_or_default();
+
+    let resp = state
+        .lock()
+        .expect("mock server state")
+        .respond(&parts, &body);
+
+    Ok(resp)
+}
+
+struct Checkout {
+    token: String,
+    payment: Option<Value>,
+    expired: bool,
+}
+
+struct State {
+    api_key: String,
+    secret_key: String,
+    next_id: u64,
+    members: Vec<Member>,
+    payments: Vec<Value>,
+    checkouts: Vec<Checkout>,
+    errors: Vec<(Method, String, MockError)>,
+    requests: Vec<RecordedRequest>,
+}
+
+impl State {
+    fn new(api_key: &str, secret_key: &str) -> Self {
+        Self {
+            api_key: api_key.to_owned(),
+            secret_key: secret_key.to_owned(),
+            next_id: 1,
+            members: Vec::new(),
+            payments: Vec::new(),
+            checkouts: Vec::new(),
+            errors: Vec::new(),
+            requests: Vec::new(),
+        }
+    }
+
+    fn respond(&mut self, parts: &Parts, body: &[u8]) -> Response<Body> {
+        let host = parts
+            .headers
+            .get(HOST)
+            .and_then(|h| h.to_str().ok())
+            .unwrap_or_default();
+        let path_and_query = parts
+            .uri
+            .path_and_query()
+            .map(|p| p.as_str())
+            .unwrap_or("/");
+        let url = format!("http://{}{}", host, path_and_query);
+        let path = parts.uri.path().to_owned();
+
+        self.requests.push(RecordedRequest {
+            method: parts.method.clone(),
+            path: path.clone(),
+            query: parts.uri.query().map(ToOwned::to_owned),
+            body: serde_json::from_slice(body).ok(),
+        });
+
+        if !self.verify_signature(&url, &parts.headers, body) {
+            return error(
+                &MockError::validation(UNAUTHORIZED_ERROR_CODE, "Unauthorized request")
+                    .with_status(StatusCode::UNAUTHORIZED),
+            );
+        }
+
+        if let Some(idx) = self
+            .errors
+            .iter()
+            .position(|(method, p, _)| method == parts.method && *p == path)
+        {
+            let (_, _, err) = self.errors.remove(idx);
+            return error(&err);
+        }
+
+        let url = Url::parse(&url).expect("valid url");
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+        match (&parts.method, segments.as_slice()) {
+            (&Method::POST, ["onboarding", "v1", "members"]) => self.create_member(body),
+            (&Method::GET, ["onboarding", "v1", "members"]) => self.search_members(&url),
+            (&Method::GET, ["onboarding", "v1", "members", id]) => self.retrieve_member(id),
+            (&Method::PUT, ["onboarding", "v1", "members", id]) => self.update_member(id, body),
+            (&Method::POST, ["payment", "v1", "checkout-payments", "init"]) => {
+                self.init_checkout_payment(&url, body)
+            }
+            (&Method::GET, ["payment", "v1", "checkout-payments", token]) => {
+                self.checkout_payment_inquiry(token)
+            }
+            (&Method::DELETE, ["payment", "v1", "checkout-payments", token]) => {
+                self.expire_checkout_payment(token)
+            }
+            (&Method::GET, ["payment", "v1", "card-payments"]) => self.search_payments(&url),
+            (&Method::GET, ["payment", "v1", "card-payments", id]) => self.retrieve_payment(id),
+            _ => error(
+                &MockError::validation(NOT_FOUND_ERROR_CODE, "Endpoint not found")
+                    .with_status(StatusCode::NOT_FOUND),
+            ),
+        }
+    }
+
+    fn verify_signature(&self, url: &str, headers: &HeaderMap, body: &[u8]) -> bool {
+        let header = |name: HeaderName| headers.get(name).and_then(|v| v.to_str().ok());
+
+        let (Some(api_key), Some(random_string), Some(sig)) =
+            (header(X_API_KEY), header(X_RND_KEY), header(X_SIGNATURE))
+        else {
+            return false;
+        };
+
+        let body = (!body.is_empty()).then_some(body);
+        let expected = signature(url, &self.api_key, &self.secret_key, random_string, body);
+
+        api_key == self.api_key && sig == expected
+    }
+
+    fn next_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    fn create_member(&mut self, body: &[u8]) -> Response<Body> {
+        let Ok(req) = serde_json::from_slice::<CreateMemberRequest>(body) else {
+            return invalid_request();
+        };
+
+        let member = Member {
+            id: MemberId(self.next_id()),
+            created_date: OffsetDateTime::now_utc(),
+            updated_date: None,
+            status: Status::Active,
+            is_buyer: req.is_buyer,
+            is_sub_merchant: req.is_sub_merchant,
+            member_type: req.member_type,
+            member_external_id: req.member_external_id,
+            name: req.name,
+            address: req.address,
+            email: req.email,
+            iban: req.iban,
+            phone_number: req.phone_number,
+            legal_company_title: req.legal_company_title,
+            tax_office: req.tax_office,
+            tax_number: req.tax_number,
+            contact_name: req.contact_name,
+            contact_surname: req.contact_surname,
+            identity_number: req.identity_number,
+            sub_merchant_maximum_allowed_negative_balance: req
+                .sub_merchant_maximum_allowed_negative_balance
+                as f64,
+        };
+
+        self.members.push(member.clone());
+
+        data(member)
+    }
+
+    fn update_member(&mut self, id: &str, body: &[u8]) -> Response<Body> {
+        let Ok(req) = serde_json::from_slice::<UpdateMemberRequest>(body) else {
+            return invalid_request();
+        };
+        let Some(member) = self
+            .members
+            .iter_mut()
+            .find(|m| id.parse::<MemberId>().ok() == Some(m.id))
+        else {
+            return not_found("Member not found");
+        };
+
+        member.updated_date = Some(OffsetDateTime::now_utc());
+        member.name = Some(req.name);
+        member.email = req.email;
+        member.address = req.address;
+        member.contact_name = Some(req.contact_name);
+        member.contact_surname = Some(req.contact_surname);
+        member.is_buyer = req.is_buyer.or(member.is_buyer);
+        member.is_sub_merchant = req.is_sub_merchant.or(member.is_sub_merchant);
+        member.member_type = req.member_type.or(member.member_type.take());
+        if let Some(phone_number) = req.phone_number {
+            member.phone_number = phone_number;
+        }
+        member.identity_number = req.identity_number.or(member.identity_number.take());
+        member.legal_company_title = req
+            .legal_company_title
+            .or(member.legal_company_title.take());
+        member.tax_office = req.tax_office.or(member.tax_office.take());
+        member.tax_number = req.tax_number.or(member.tax_number.take());
+        member.iban = req.iban.or(member.iban.take());
+        if let Some(limit) = req.sub_merchant_maximum_allowed_negative_balance {
+            member.sub_merchant_maximum_allowed_negative_balance = limit as f64;
+        }
+
+        data(member.clone())
+    }
+
+    fn retrieve_member(&self, id: &str) -> Response<Body> {
+        let member = self
+            .members
+            .iter()
+            .find(|m| id.parse::<MemberId>().ok() == Some(m.id));
+
+        data(member)
+    }
+
+    fn search_members(&self, url: &Url) -> Response<Body> {
+        let mut members: Vec<&Member> = self.members.iter().collect();
+
+        for (key, value) in url.query_pairs() {
+            match key.as_ref() {
+                "memberExternalId" => members.retain(|m| m.member_external_id == value),
+                "name" => members.retain(|m| m.name.as_deref() == Some(value.as_ref())),
+                "isBuyer" => members.retain(|m| value.parse::<bool>().ok() == m.is_buyer),
+                "isSubMerchant" => {
+                    members.retain(|m| value.parse::<bool>().ok() == m.is_sub_merchant)
+                }
+                _ => {}
+            }
+        }
+
+        paginated(url, members)
+    }
+
+    fn init_checkout_payment(&mut self, url: &Url, body: &[u8]) -> Response<Body> {
+        let Ok(req) = serde_json::from_slice::<CheckoutPaymentInitiationRequest>(body) else {
+            return invalid_request();
+        };
+
+        let token: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(36)
+            .map(char::from)
+            .collect();
+        let ttl = req.ttl.map_or(DEFAULT_CHECKOUT_TTL, |ttl| ttl as i64);
+        let expire_date =
+            date::format(OffsetDateTime::now_utc() + Duration::seconds(ttl)).expect("valid date");
+
+        let mut page_url = url.join("/checkout").expect("valid url");
+        page_url.set_query(Some(&format!("token={}", token)));
+
+        self.checkouts.push(Checkout {
+            token: token.clone(),
+            payment: None,
+            expired: false,
+        });
+
+        data(json!({
+            "token": token,
+            "pageUrl": page_url.as_str(),
+            "tokenExpireDate": expire_date,
+        }))
+    }
+
+    fn checkout_payment_inquiry(&self, token: &str) -> Response<Body> {
+        let checkout = self
+            .checkouts
+            .iter()
+            .find(|c| c.token == token && !c.expired);
+
+        match checkout.map(|c| c.payment.as_ref()) {
+            Some(Some(payment)) => data(payment),
+            Some(None) => error(&MockError::validation(
+                PAYMENT_NOT_COMPLETED.0,
+                "Payment is not completed yet",
+            )),
+            None => not_found("Checkout form not found"),
+        }
+    }
+
+    fn expire_checkout_payment(&mut self, token: &str) -> Response<Body> {
+        let Some(checkout) = self.checkouts.iter_mut().find(|c| c.token == token) else {
+            return not_found("Checkout payment not found");
+        };
+
+        checkout.expired = true;
+
+        Response::new(Body::empty())
+    }
+
+    fn retrieve_payment(&self, id: &str) -> Response<Body> {
+        let payment = id
+            .parse::<u64>()
+            .ok()
+            .and_then(|id| self.payments.iter().find(|p| p["id"] == id));
+
+        match payment {
+            Some(payment) => data(payment),
+            None => not_found("Payment not found"),
+        }
+    }
+
+    fn search_payments(&self, url: &Url) -> Response<Body> {
+        let mut payments: Vec<&Value> = self.payments.iter().collect();
+
+        for (key, value) in url.query_pairs() {
+            match key.as_ref() {
+                "conversationId" | "externalId" | "paymentStatus" | "paymentType" | "currency" => {
+                    payments.retain(|p| p[key.as_ref()] == value.as_ref())
+                }
+                "buyerMemberId" => {
+                    payments.retain(|p| value.parse::<u64>().ok() == p["buyerMemberId"].as_u64())
+                }
+                _ => {}
+            }
+        }
+
+        paginated(url, payments)
+    }
+}
+
+fn json_response(status: StatusCode, body: Value) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .expect("valid response")
+}
+
+fn data<T: Serialize>(data: T) -> Response<Body> {
+    json_response(StatusCode::OK, json!({ "data": data }))
+}
+
+fn paginated<T: Serialize>(url: &Url, items: Vec<T>) -> Response<Body> {
+    let query = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .and_then(|(_, value)| value.parse::<usize>().ok())
+    };
+    let page = query("page").unwrap_or(0);
+    let size = query("size").unwrap_or(25);
+    let total_size = items.len();
+
+    let items: Vec<T> = items.into_iter().skip(page * size).take(size).collect();
+
+    data(json!({
+        "items": items,
+        "page": page,
+        "size": size,
+        "totalSize": total_size,
+    }))
+}
+
+fn error(err: &MockError) -> Response<Body> {
+    json_response(
+        err.status,
+        json!({
+            "errors": {
+                "errorCode": err.code.to_string(),
+                "errorDescription": err.description,
+                "errorGroup": err.group,
+            }
+        }),
+    )
+}
+
+fn not_found(description: &str) -> Response<Body> {
+    error(&MockError::validation(NOT_FOUND_ERROR_CODE, description))
+}
+
+fn invalid_request() -> Response<Body> {
+    error(&MockError::validation(
+        INVALID_REQUEST_ERROR_CODE,
+        "Request body could not be parsed",
+    ))
+}
@@ -0,0 +1,38 @@
+#[cfg(test)]
+use crate::{
+    id::MemberId,
+    request::bank_account_tracking::{
+        search_records::SearchBankAccountTrackingRecordsRequest, RecordType,
+    },
+    tests::get_fixture_client,
+};
+#[cfg(test)]
+use rust_decimal::Decimal;
+
+#[tokio::test]
+async fn test_search_and_retrieve_records() {
+    let client = get_fixture_client("bank_account_tracking/records.json");
+
+    let records = client
+        .search_bank_account_tracking_records(
+            SearchBankAccountTrackingRecordsRequest::builder()
+                .record_type(Some(RecordType::Receive))
+                .build(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(records.items.len(), 1);
+
+    let record = client
+        .retrieve_bank_account_tracking_record(records.items[0].id)
+        .await
+        .unwrap();
+
+    assert_eq!(record.amount, Decimal::new(125_050, 2));
+    assert_eq!(record.record_type, RecordType::Receive);
+    assert_eq!(record.member_id, Some(MemberId(116214)));
+    assert_eq!(
+        record.sender_iban.as_deref(),
+        Some("TR330006100519786457841326")
+    );
+}
@@ -0,0 +1,59 @@
+#[cfg(test)]
+use crate::{
+    blocking::CraftgateClient,
+    client::CraftgateError,
+    id::MemberId,
+    request::onboarding::create_member::CreateMemberRequest,
+    testing::{MockCraftgateServer, UNAUTHORIZED_ERROR_CODE},
+};
+#[cfg(test)]
+use assert_matches::assert_matches;
+#[cfg(test)]
+use tokio::runtime::Runtime;
+
+/// The mock server runs on a runtime of its own, the blocking client is used from the test thread
+/// outside of it.
+#[cfg(test)]
+fn start_server(runtime: &Runtime) -> MockCraftgateServer {
+    let _guard = runtime.enter();
+
+    MockCraftgateServer::start("api-key", "secret-key")
+}
+
+#[test]
+fn test_blocking_member_lifecycle() {
+    let runtime = Runtime::new().unwrap();
+    let server = start_server(&runtime);
+    let client = CraftgateClient::new_with_base_url(server.base_url(), "api-key", "secret-key");
+
+    let member = client
+        .create_member(
+            CreateMemberRequest::builder()
+                .member_external_id("d8fa867b-000b-4b96-ad3c-43ea22e65e3f".to_owned())
+                .address("Suadiye Mah. Örnek Cd. No:23, 34740 Kadıköy/İstanbul".to_owned())
+                .email("haluk.demir@example.com".to_owned())
+                .phone_number("905551111111".to_owned())
+                .build(),
+        )
+        .unwrap();
+
+    let retrieved = client.retrieve_member(member.id).unwrap();
+    assert_matches!(retrieved, Some(retrieved) => {
+        assert_eq!(retrieved.email, "haluk.demir@example.com");
+    });
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn test_blocking_rejects_invalid_signature() {
+    let runtime = Runtime::new().unwrap();
+    let server = start_server(&runtime);
+    let client =
+        CraftgateClient::new_with_base_url(server.base_url(), "api-key", "wrong-secret-key");
+
+    let err = client.retrieve_member(MemberId(1)).unwrap_err();
+
+    assert_matches!(err, CraftgateError::ValidationError { code, .. } => {
+        assert_eq!(code.as_ref(), &UNAUTHORIZED_ERROR_CODE);
+    });
+}
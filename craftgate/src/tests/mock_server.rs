@@ -0,0 +1,540 @@
+#[cfg(feature = "retry")]
+use crate::retry::RetryPolicy;
+#[cfg(test)]
+use crate::{
+    api_error::{ErrorCode, ErrorGroup},
+    checkout::{CheckoutSession, PAYMENT_NOT_COMPLETED},
+    client::{ClientOptions, CraftgateClient, CraftgateError},
+    id::{CheckoutToken, MemberId, PaymentId},
+    idempotency::IdempotencyKey,
+    request::onboarding::{
+        create_member::CreateMemberRequest, search_members::SearchMembersRequest,
+        update_member::UpdateMemberRequest,
+    },
+    request::payment::checkout_payment::{
+        CheckoutPaymentInitiationRequest, CheckoutPaymentInitiationResponse,
+    },
+    request::payment::{create_payment::CreatePaymentRequest, Card, PaymentItem},
+    request::validation::ViolationKind,
+    testing::{MockCraftgateServer, MockError, NOT_FOUND_ERROR_CODE, UNAUTHORIZED_ERROR_CODE},
+};
+#[cfg(test)]
+use assert_matches::assert_matches;
+#[cfg(test)]
+use http::Method;
+#[cfg(feature = "retry")]
+use http::StatusCode;
+#[cfg(test)]
+use rust_decimal::Decimal;
+#[cfg(test)]
+use std::time::Duration;
+#[cfg(test)]
+use time::macros::datetime;
+
+#[cfg(test)]
+fn start_server() -> MockCraftgateServer {
+    MockCraftgateServer::start("api-key", "secret-key")
+}
+
+#[cfg(test)]
+fn create_member_request() -> CreateMemberRequest {
+    CreateMemberRequest::builder()
+        .member_external_id("d8fa867b-000b-4b96-ad3c-43ea22e65e3f".to_owned())
+        .address("Suadiye Mah. Örnek Cd. No:23, 34740 Kadıköy/İstanbul".to_owned())
+        .email("haluk.demir@example.com".to_owned())
+        .phone_number("905551111111".to_owned())
+        .contact_name(Some("Haluk".to_owned()))
+        .contact_surname(Some("Demir".to_owned()))
+        .is_buyer(Some(true))
+        .build()
+}
+
+#[cfg(test)]
+fn checkout_payment_request() -> CheckoutPaymentInitiationRequest {
+    CheckoutPaymentInitiationRequest::builder()
+        .price(Decimal::new(100, 0))
+        .paid_price(Decimal::new(100, 0))
+        .conversation_id(Some("456d1297-908e-4bd6-a13b-4be31a6e47d5".to_owned()))
+        .callback_url("https://www.your-website.com/craftgate-checkout-callback".to_owned())
+        .items(vec![PaymentItem::builder()
+            .price(Decimal::new(100, 0))
+            .build()])
+        .build()
+}
+
+#[cfg(test)]
+fn payment_json(id: u64, conversation_id: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "createdDate": "2023-10-27T14:03:01",
+        "price": 100,
+        "paidPrice": 100,
+        "walletPrice": 0,
+        "currency": "TRY",
+        "buyerMemberId": null,
+        "installment": 1,
+        "conversationId": conversation_id,
+        "externalId": null,
+        "paymentType": "CARD_PAYMENT",
+        "paymentGroup": "PRODUCT",
+        "paymentSource": "CHECKOUT_FORM",
+        "paymentStatus": "SUCCESS",
+        "paymentPhase": "AUTH",
+        "paymentChannel": null,
+        "isThreeDS": false,
+        "merchantCommissionRate": 0,
+        "merchantCommissionRateAmount": 0,
+        "bankCommissionRate": 0.01,
+        "bankCommissionRateAmount": 1,
+        "cardUserKey": null,
+        "cardToken": null,
+        "paidWithStoredCard": false,
+        "binNumber": "52600100",
+        "lastFourDigits": "0026",
+        "cardHolderName": "Haluk Demir",
+        "bankCardHolderName": "Haluk Demir",
+        "authCode": "867440",
+        "hostReference": "mock:host-reference",
+        "transId": "mock:trans-id",
+        "orderId": "mock:order-id",
+        "cardType": "CREDIT_CARD",
+        "cardAssociation": "MASTER_CARD",
+        "cardBrand": "World",
+        "requestedPosAlias": null,
+        "pos": {
+            "id": 1,
+            "name": "Mock POS",
+            "alias": "mock-pos",
+            "bankId": 1
+        },
+        "loyalty": null,
+        "fraudId": null,
+        "fraudAction": null,
+        "paymentTransactions": [],
+        "additionalData": null
+    })
+}
+
+#[tokio::test]
+async fn test_member_lifecycle() {
+    let server = start_server();
+    let client = server.client();
+
+    let member = client.create_member(create_member_request()).await.unwrap();
+    assert_eq!(member.email, "haluk.demir@example.com");
+
+    let update = UpdateMemberRequest::builder()
+        .name("Dem Zeytinyağı Üretim Ltd. Şti.".to_owned())
+        .email("info@dem.example.com".to_owned())
+        .address("Suadiye Mah. Örnek Cd. No:23, 34740 Kadıköy/İstanbul".to_owned())
+        .contact_name("Haluk".to_owned())
+        .contact_surname("Demir".to_owned())
+        .build();
+    let updated = client.update_member(member.id, update).await.unwrap();
+    assert_eq!(updated.email, "info@dem.example.com");
+
+    let retrieved = client.retrieve_member(member.id).await.unwrap();
+    assert_matches!(retrieved, Some(retrieved) => {
+        assert_eq!(retrieved.email, "info@dem.example.com");
+    });
+
+    let params = SearchMembersRequest::builder()
+        .member_external_id(Some(member.member_external_id.clone()))
+        .size(25)
+        .build();
+    let members = client.search_members(params).await.unwrap();
+    assert_eq!(members.items.len(), 1);
+    assert_eq!(members.total_size, 1);
+}
+
+#[tokio::test]
+async fn test_rejects_invalid_signature() {
+    let server = start_server();
+    let client = CraftgateClient::new_with_options(
+        "api-key",
+        "wrong-secret-key",
+        ClientOptions::builder()
+            .base_url(Some(server.base_url()))
+            .build(),
+    );
+
+    let err = client.retrieve_member(MemberId(1)).await.unwrap_err();
+
+    assert_matches!(err, CraftgateError::ValidationError { code, .. } => {
+        assert_eq!(code.as_ref(), &UNAUTHORIZED_ERROR_CODE);
+    });
+}
+
+#[tokio::test]
+async fn test_scripted_payment_error() {
+    let server = start_server();
+    server.fail_next(
+        Method::POST,
+        "/payment/v1/checkout-payments/init",
+        MockError::payment(
+            10051,
+            ErrorGroup::NotSufficientFunds,
+            "Insufficient card limit, insufficient balance",
+        ),
+    );
+
+    let err = server
+        .client()
+        .initiate_checkout_payment(checkout_payment_request())
+        .await
+        .unwrap_err();
+
+    assert_matches!(err, CraftgateError::PaymentError { group, .. } => {
+        assert_eq!(group, ErrorGroup::NotSufficientFunds);
+    });
+}
+
+#[cfg(feature = "retry")]
+#[tokio::test]
+async fn test_retries_safe_requests_only() {
+    let server = start_server();
+    let client = server.client_with_options(
+        ClientOptions::builder()
+            .retry_policy(
+                RetryPolicy::builder()
+                    .min_backoff(Duration::from_millis(1))
+                    .build(),
+            )
+            .build(),
+    );
+    let unavailable = MockError::validation(0, "Service unavailable")
+        .with_status(StatusCode::SERVICE_UNAVAILABLE);
+
+    server.fail_next(Method::GET, "/onboarding/v1/members/1", unavailable.clone());
+    assert_matches!(client.retrieve_member(MemberId(1)).await, Ok(None));
+
+    server.fail_next(Method::POST, "/onboarding/v1/members", unavailable);
+    assert_matches!(
+        client.create_member(create_member_request()).await,
+        Err(CraftgateError::ValidationError { .. })
+    );
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+}
+
+#[cfg(feature = "retry")]
+#[tokio::test]
+async fn test_retries_rate_limited_mutations() {
+    let server = start_server();
+    let client = server.client_with_options(
+        ClientOptions::builder()
+            .retry_policy(
+                RetryPolicy::builder()
+                    .min_backoff(Duration::from_millis(1))
+                    .build(),
+            )
+            .build(),
+    );
+
+    server.fail_next(
+        Method::POST,
+        "/onboarding/v1/members",
+        MockError::validation(0, "Too many requests").with_status(StatusCode::TOO_MANY_REQUESTS),
+    );
+    client.create_member(create_member_request()).await.unwrap();
+
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_checkout_payment_lifecycle() {
+    let server = start_server();
+    let client = server.client();
+
+    let checkout = client
+        .initiate_checkout_payment(checkout_payment_request())
+        .await
+        .unwrap();
+
+    let err = client
+        .checkout_payment_inquiry(&checkout.token)
+        .await
+        .unwrap_err();
+    assert_matches!(err, CraftgateError::ValidationError { code, .. } => {
+        assert_eq!(code, PAYMENT_NOT_COMPLETED);
+    });
+
+    server.complete_checkout(
+        &checkout.token,
+        &payment_json(1, "456d1297-908e-4bd6-a13b-4be31a6e47d5"),
+    );
+
+    let payment = client
+        .checkout_payment_inquiry(&checkout.token)
+        .await
+        .unwrap();
+    let retrieved = client.retrieve_payment(payment.id).await.unwrap();
+    assert_eq!(retrieved.id, PaymentId(1));
+
+    client
+        .expire_common_page_token(&checkout.token)
+        .await
+        .unwrap();
+    assert!(client
+        .checkout_payment_inquiry(&checkout.token)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_checkout_session_polls_until_paid() {
+    let server = start_server();
+    let client = server.client();
+
+    let session = CheckoutSession::start(&client, checkout_payment_request())
+        .await
+        .unwrap();
+    assert!(!session.is_expired());
+    assert!(session.page_url().contains(session.token().as_str()));
+
+    let token = session.token().clone();
+    let payment = session
+        .poll(Duration::from_millis(1), |_| {
+            server.complete_checkout(
+                &token,
+                &payment_json(1, "456d1297-908e-4bd6-a13b-4be31a6e47d5"),
+            );
+            async {}
+        })
+        .await
+        .unwrap();
+    assert_matches!(payment, Some(payment) => {
+        assert_eq!(payment.id, PaymentId(1));
+    });
+
+    session.cancel().await.unwrap();
+    assert!(client.checkout_payment_inquiry(&token).await.is_err());
+}
+
+#[tokio::test]
+async fn test_expired_checkout_session_stops_polling() {
+    let server = start_server();
+    let client = server.client();
+
+    let session = CheckoutSession::new(
+        &client,
+        CheckoutPaymentInitiationResponse {
+            token: CheckoutToken::from("expired-token"),
+            page_url: "https://sandbox-checkout.craftgate.io".to_owned(),
+            token_expire_date: datetime!(2023-10-27 14:03:01 +3),
+        },
+    );
+
+    let payment = session
+        .poll(Duration::from_millis(1), |_| async {})
+        .await
+        .unwrap();
+
+    assert!(payment.is_none());
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_checkout_session_stops_polling_unknown_token() {
+    let server = start_server();
+    let client = server.client();
+
+    let session = CheckoutSession::new(
+        &client,
+        CheckoutPaymentInitiationResponse {
+            token: CheckoutToken::from("unknown-token"),
+            page_url: "https://sandbox-checkout.craftgate.io".to_owned(),
+            token_expire_date: datetime!(2099-01-01 00:00:00 +3),
+        },
+    );
+
+    let err = session
+        .poll(Duration::from_millis(1), |_| async {})
+        .await
+        .unwrap_err();
+
+    assert_matches!(err, CraftgateError::ValidationError { code, .. } => {
+        assert_eq!(code, ErrorCode(NOT_FOUND_ERROR_CODE));
+    });
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_invalid_checkout_payment_is_not_sent() {
+    let server = start_server();
+    let client = server.client();
+
+    let mut request = checkout_payment_request();
+    request.paid_price = Decimal::new(90, 0);
+
+    let err = client
+        .initiate_checkout_payment(request.clone())
+        .await
+        .unwrap_err();
+    assert_matches!(err, CraftgateError::InvalidRequest(violations) => {
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].field, "paid_price");
+        assert_eq!(violations[0].kind, ViolationKind::PaidPriceLowerThanPrice);
+    });
+    assert!(server.requests().is_empty());
+
+    client
+        .initiate_checkout_payment_unchecked(request)
+        .await
+        .unwrap();
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_reconciles_ambiguous_payment_failure() {
+    let server = start_server();
+    let client = server.client();
+    server.insert_payment(&payment_json(7, "conversation-7"));
+
+    let mut attempts = 0;
+    let payment = client
+        .execute_idempotent(
+            &IdempotencyKey::new("order-7").unwrap(),
+            || {
+                attempts += 1;
+                async {
+                    Err(CraftgateError::PaymentError {
+                        code: ErrorCode(10000),
+                        description: "Request timed out".to_owned(),
+                        group: ErrorGroup::RequestTimeout,
+                    })
+                }
+            },
+            |_| client.find_payment(Some("conversation-7"), None, None),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(payment.id, PaymentId(7));
+    assert_eq!(attempts, 1);
+}
+
+#[cfg(test)]
+fn bank_timeout() -> MockError {
+    MockError::payment(10000, ErrorGroup::RequestTimeout, "Request timed out")
+}
+
+#[cfg(test)]
+fn create_payment_request(external_id: Option<&str>) -> CreatePaymentRequest {
+    CreatePaymentRequest::builder()
+        .price(Decimal::new(100, 0))
+        .paid_price(Decimal::new(100, 0))
+        .conversation_id(Some("conversation-7".to_owned()))
+        .external_id(external_id.map(ToOwned::to_owned))
+        .card(Card::default())
+        .items(vec![PaymentItem::builder()
+            .price(Decimal::new(100, 0))
+            .build()])
+        .build()
+}
+
+#[tokio::test]
+async fn test_reconciles_created_payment_by_external_id() {
+    let server = start_server();
+    let client = server.client();
+    server.fail_next(Method::POST, "/payment/v1/card-payments", bank_timeout());
+    // Earlier payment of the merchant that reused the conversation id
+    server.insert_payment(&payment_json(6, "conversation-7"));
+    let mut payment = payment_json(7, "conversation-7");
+    payment["externalId"] = "order-7".into();
+    server.insert_payment(&payment);
+
+    let payment = client
+        .create_payment(
+            create_payment_request(Some("order-7")),
+            &IdempotencyKey::new("order-7").unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(payment.id, PaymentId(7));
+    let sent = server
+        .requests()
+        .iter()
+        .filter(|request| request.method == Method::POST)
+        .count();
+    assert_eq!(sent, 1);
+}
+
+#[tokio::test]
+async fn test_does_not_reconcile_payments_without_external_id() {
+    let server = start_server();
+    let client = server.client();
+    server.fail_next(Method::POST, "/payment/v1/card-payments", bank_timeout());
+    server.insert_payment(&payment_json(6, "conversation-7"));
+
+    let err = client
+        .create_payment(
+            create_payment_request(None),
+            &IdempotencyKey::new("order-7").unwrap(),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(err.is_ambiguous());
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_does_not_resend_3ds_completion_in_progress() {
+    let server = start_server();
+    let client = server.client();
+    server.fail_next(
+        Method::POST,
+        "/payment/v1/card-payments/3ds-complete",
+        bank_timeout(),
+    );
+    let mut payment = payment_json(7, "conversation-7");
+    payment["paymentStatus"] = "INIT_THREEDS".into();
+    server.insert_payment(&payment);
+
+    let err = client
+        .complete_3ds_payment(PaymentId(7), &IdempotencyKey::new("order-7").unwrap())
+        .await
+        .unwrap_err();
+
+    assert_matches!(
+        err,
+        CraftgateError::PaymentError {
+            group: ErrorGroup::RequestTimeout,
+            ..
+        }
+    );
+    let sent = server
+        .requests()
+        .iter()
+        .filter(|request| request.method == Method::POST)
+        .count();
+    assert_eq!(sent, 1);
+}
+
+#[tokio::test]
+async fn test_find_payment_ignores_failed_payments() {
+    let server = start_server();
+    let client = server.client();
+    let mut failed = payment_json(7, "conversation-7");
+    failed["paymentStatus"] = "FAILURE".into();
+    server.insert_payment(&failed);
+    server.insert_payment(&payment_json(8, "conversation-7"));
+
+    let payment = client
+        .find_payment(Some("conversation-7"), None, None)
+        .await
+        .unwrap();
+    assert_eq!(payment.map(|p| p.id), Some(PaymentId(8)));
+
+    server.insert_payment(&payment_json(9, "conversation-7"));
+    let err = client
+        .find_payment(Some("conversation-7"), None, None)
+        .await
+        .unwrap_err();
+    assert_matches!(err, CraftgateError::AmbiguousPayment(ids) => {
+        assert_eq!(ids, vec![PaymentId(8), PaymentId(9)]);
+    });
+}
@@ -1,12 +1,102 @@
-#[cfg(test)]
-use crate::client::CraftgateClient;
+#[cfg(all(
+    feature = "reqwest",
+    any(
+        feature = "onboarding",
+        feature = "payment",
+        feature = "settlement",
+        feature = "bank-account-tracking"
+    )
+))]
+use std::{path::Path, sync::Arc};
 
+#[cfg(all(
+    feature = "reqwest",
+    any(
+        feature = "onboarding",
+        feature = "payment",
+        feature = "settlement",
+        feature = "bank-account-tracking"
+    )
+))]
+use reqwest_middleware::Middleware;
+
+#[cfg(all(
+    feature = "reqwest",
+    any(
+        feature = "onboarding",
+        feature = "payment",
+        feature = "settlement",
+        feature = "bank-account-tracking"
+    )
+))]
+use crate::{
+    client::{ClientOptions, CraftgateClient},
+    testing::FixtureMiddleware,
+};
+
+#[cfg(all(feature = "bank-account-tracking", feature = "reqwest"))]
+mod bank_account_tracking;
+#[cfg(all(feature = "blocking", feature = "onboarding", feature = "payment"))]
+mod blocking;
+#[cfg(all(feature = "onboarding", feature = "payment", feature = "reqwest"))]
+mod mock_server;
+#[cfg(all(feature = "onboarding", feature = "reqwest"))]
 mod onboarding;
+#[cfg(all(feature = "payment", feature = "reqwest"))]
+mod payment;
+#[cfg(all(feature = "payment", feature = "settlement", feature = "reqwest"))]
+mod reconciliation;
+#[cfg(all(feature = "reporting", feature = "reqwest"))]
+mod reporting;
+#[cfg(all(feature = "settlement", feature = "reqwest"))]
+mod settlement;
+#[cfg(feature = "onboarding")]
+mod transport;
+#[cfg(all(feature = "wallet", feature = "reqwest"))]
+mod wallet;
+
+/// Client for tests backed by a fixture file in `fixtures/`. The fixture is replayed, unless
+/// `RECORD_FIXTURES` is set. Then requests are sent to the sandbox using the keys from `.env` and
+/// the fixture is recorded again.
+#[cfg(all(
+    feature = "reqwest",
+    any(
+        feature = "onboarding",
+        feature = "payment",
+        feature = "settlement",
+        feature = "bank-account-tracking"
+    )
+))]
+fn get_fixture_client(fixture: &str) -> CraftgateClient {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(fixture);
+
+    if std::env::var_os("RECORD_FIXTURES").is_some() {
+        dotenvy::dotenv().ok();
+        let api_key = std::env::var("API_KEY").expect("API_KEY");
+        let secret_key = std::env::var("SECRET_KEY").expect("SECRET_KEY");
+        let middleware: Arc<dyn Middleware> = Arc::new(FixtureMiddleware::record(path));
+
+        return CraftgateClient::new_with_options(
+            &api_key,
+            &secret_key,
+            ClientOptions::builder()
+                .sandbox(true)
+                .middleware(vec![middleware])
+                .build(),
+        );
+    }
+
+    let middleware: Arc<dyn Middleware> =
+        Arc::new(FixtureMiddleware::replay(path).expect("fixture is recorded"));
 
-#[cfg(test)]
-fn get_test_client() -> CraftgateClient {
-    dotenvy::dotenv().ok();
-    let api_key = std::env::var("API_KEY").expect("API_KEY");
-    let secret_key = std::env::var("SECRET_KEY").expect("SECRET_KEY");
-    CraftgateClient::new(true, &api_key, &secret_key)
+    CraftgateClient::new_with_options(
+        "fixture-api-key",
+        "fixture-secret-key",
+        ClientOptions::builder()
+            .sandbox(true)
+            .middleware(vec![middleware])
+            .build(),
+    )
 }
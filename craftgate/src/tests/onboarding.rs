@@ -1,16 +1,16 @@
 #[cfg(test)]
 use crate::{
+    id::MemberId,
     request::onboarding::{
         create_member::CreateMemberRequest, search_members::SearchMembersRequest,
         update_member::UpdateMemberRequest, MemberType,
     },
-    tests::get_test_client,
+    tests::get_fixture_client,
 };
 #[cfg(test)]
 use assert_matches::assert_matches;
 
 #[tokio::test]
-#[ignore]
 async fn test_create_member() {
     let member = CreateMemberRequest {
         is_buyer: Some(true),
@@ -27,7 +27,10 @@ async fn test_create_member() {
         ..Default::default()
     };
 
-    let member = get_test_client().create_member(member).await.unwrap();
+    let member = get_fixture_client("onboarding/create_member.json")
+        .create_member(member)
+        .await
+        .unwrap();
 
     assert_eq!(
         member.member_external_id,
@@ -36,7 +39,6 @@ async fn test_create_member() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_update_member() {
     let member = UpdateMemberRequest::builder()
         .contact_name("Haluk".to_owned())
@@ -55,8 +57,8 @@ async fn test_update_member() {
         .is_sub_merchant(Some(false))
         .build();
 
-    let member = get_test_client()
-        .update_member(89508, member)
+    let member = get_fixture_client("onboarding/update_member.json")
+        .update_member(MemberId(89508), member)
         .await
         .unwrap();
 
@@ -67,9 +69,11 @@ async fn test_update_member() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_retrieve_member() {
-    let member = get_test_client().retrieve_member(89508).await.unwrap();
+    let member = get_fixture_client("onboarding/retrieve_member.json")
+        .retrieve_member(MemberId(89508))
+        .await
+        .unwrap();
 
     assert_matches!(member, Some(member) => {
         assert_eq!(member.member_external_id, "d8fa867b-000b-4b96-ad3c-43ea22e65e3f".to_owned());
@@ -77,14 +81,16 @@ async fn test_retrieve_member() {
 }
 
 #[tokio::test]
-#[ignore]
 async fn test_search_members() {
     let params = SearchMembersRequest::builder()
         .member_external_id(Some("d8fa867b-000b-4b96-ad3c-43ea22e65e3f".to_owned()))
         .size(25)
         .build();
 
-    let members = get_test_client().search_members(params).await.unwrap();
+    let members = get_fixture_client("onboarding/search_members.json")
+        .search_members(params)
+        .await
+        .unwrap();
 
     assert_eq!(members.items.len(), 1);
 }
@@ -0,0 +1,542 @@
+#[cfg(test)]
+use crate::{
+    id::PaymentId,
+    idempotency::IdempotencyKey,
+    request::money::Money,
+    request::payment::{
+        apple_pay::ApplePayMerchantSessionRequest,
+        bkm_express::{CompleteBkmExpressRequest, InitBkmExpressRequest},
+        checkout_payment::CheckoutPaymentInitiationRequest,
+        create_payment::CreatePaymentRequest,
+        garanti_pay::{GarantiPayInstallment, InitGarantiPayPaymentRequest},
+        masterpass::{
+            CheckMasterpassUserRequest, MasterpassCreatePayment, MasterpassPaymentCompleteRequest,
+            MasterpassPaymentThreeDSInitRequest, MasterpassPaymentTokenGenerateRequest,
+            MasterpassRetrieveLoyaltiesRequest,
+        },
+        multi_payment::MultiPaymentStatus,
+        retrieve_loyalties::RetrieveLoyaltiesRequest,
+        three_ds_payment::InitThreeDSPaymentRequest,
+        Card, CardAssociation, LoyaltyType, PaymentItem, PaymentSource, PaymentStatus, PaymentType,
+        WalletToken, WalletTokenType,
+    },
+    request::Currency,
+    tests::get_fixture_client,
+};
+#[cfg(test)]
+use assert_matches::assert_matches;
+#[cfg(test)]
+use rust_decimal::Decimal;
+#[cfg(test)]
+use time::macros::{datetime, offset};
+
+#[cfg(test)]
+fn card() -> Card {
+    Card::builder()
+        .card_holder_name(Some("Haluk Demir".to_owned()))
+        .card_number(Some("5258640000000001".to_owned()))
+        .expire_year(Some("2044".to_owned()))
+        .expire_month(Some("07".to_owned()))
+        .cvc(Some("000".to_owned()))
+        .build()
+}
+
+#[cfg(test)]
+fn create_payment_request(conversation_id: &str, card: Card) -> CreatePaymentRequest {
+    CreatePaymentRequest::builder()
+        .price(Decimal::new(100, 0))
+        .paid_price(Decimal::new(100, 0))
+        .conversation_id(Some(conversation_id.to_owned()))
+        .card(card)
+        .items(vec![PaymentItem::builder()
+            .name(Some("Item 1".to_owned()))
+            .price(Decimal::new(100, 0))
+            .build()])
+        .build()
+}
+
+#[cfg(test)]
+fn masterpass_token_request(
+    conversation_id: &str,
+    force_three_d_s: bool,
+) -> MasterpassPaymentTokenGenerateRequest {
+    MasterpassPaymentTokenGenerateRequest::builder()
+        .user_id("buyer-116210".to_owned())
+        .msisdn("905551111111".to_owned())
+        .bin_number("52586400".to_owned())
+        .force_three_d_s(Some(force_three_d_s))
+        .create_payment(
+            MasterpassCreatePayment::builder()
+                .price(Decimal::new(100, 0))
+                .paid_price(Decimal::new(100, 0))
+                .conversation_id(Some(conversation_id.to_owned()))
+                .items(vec![PaymentItem::builder()
+                    .name(Some("Item 1".to_owned()))
+                    .price(Decimal::new(100, 0))
+                    .build()])
+                .build(),
+        )
+        .build()
+}
+
+#[tokio::test]
+async fn test_retrieve_card_payment() {
+    let payment = get_fixture_client("payment/retrieve_card_payment.json")
+        .retrieve_payment(PaymentId(1045210))
+        .await
+        .unwrap();
+
+    assert_matches!(payment.payment_type, PaymentType::CardPayment);
+    assert_eq!(
+        payment.paid_price(),
+        Money::new(Decimal::new(100, 0), Currency::Try).unwrap()
+    );
+    assert_eq!(payment.last_four_digits.as_deref(), Some("0000"));
+    assert_matches!(payment.pos, Some(pos) => {
+        assert_eq!(pos.name, "Sandbox POS");
+        assert_eq!(pos.bank_id, 111);
+    });
+    assert!(payment.payment_transactions[0]
+        .blockage_resolved_date
+        .is_some());
+}
+
+#[tokio::test]
+async fn test_timestamps_in_client_offset() {
+    let payment = get_fixture_client("payment/retrieve_card_payment.json")
+        .with_utc_offset(offset!(UTC))
+        .retrieve_payment(PaymentId(1045210))
+        .await
+        .unwrap();
+
+    assert_eq!(payment.created_date, datetime!(2023-11-02 10:15:42 UTC));
+}
+
+#[tokio::test]
+async fn test_retrieve_wallet_payment() {
+    let payment = get_fixture_client("payment/retrieve_wallet_payment.json")
+        .retrieve_payment(PaymentId(1045211))
+        .await
+        .unwrap();
+
+    assert_matches!(payment.payment_type, PaymentType::WalletPayment);
+    assert!(payment.bin_number.is_none());
+    assert!(payment.card_association.is_none());
+    assert!(payment.pos.is_none());
+    assert!(payment.payment_transactions[0]
+        .blockage_resolved_date
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_retrieve_apm_payment() {
+    let payment = get_fixture_client("payment/retrieve_apm_payment.json")
+        .retrieve_payment(PaymentId(1045212))
+        .await
+        .unwrap();
+
+    assert_matches!(payment.payment_type, PaymentType::Apm);
+    assert_matches!(payment.payment_source, PaymentSource::CheckoutForm);
+    assert!(payment.card_type.is_none());
+    assert!(payment.host_reference.is_none());
+    assert!(payment.pos.is_none());
+}
+
+#[tokio::test]
+async fn test_retrieve_deposit_payment() {
+    let payment = get_fixture_client("payment/retrieve_deposit_payment.json")
+        .retrieve_payment(PaymentId(1045213))
+        .await
+        .unwrap();
+
+    assert_matches!(payment.payment_type, PaymentType::DepositPayment);
+    assert!(payment.pos.is_some());
+    assert!(payment.payment_transactions.is_empty());
+}
+
+#[tokio::test]
+async fn test_retrieve_card_and_wallet_payment() {
+    let payment = get_fixture_client("payment/retrieve_card_and_wallet_payment.json")
+        .retrieve_payment(PaymentId(1045214))
+        .await
+        .unwrap();
+
+    assert_matches!(payment.payment_type, PaymentType::CardAndWalletPayment);
+    assert_eq!(
+        payment.wallet_price(),
+        Money::new(Decimal::new(40, 0), Currency::Try).unwrap()
+    );
+    assert_eq!(payment.last_four_digits.as_deref(), Some("0000"));
+    assert_eq!(
+        payment.payment_transactions[0].wallet_price,
+        Decimal::new(40, 0)
+    );
+}
+
+#[tokio::test]
+async fn test_retrieve_bank_transfer_payment() {
+    let payment = get_fixture_client("payment/retrieve_bank_transfer_payment.json")
+        .retrieve_payment(PaymentId(1045215))
+        .await
+        .unwrap();
+
+    assert_matches!(payment.payment_type, PaymentType::BankTransfer);
+    assert!(payment.bin_number.is_none());
+    assert!(payment.card_type.is_none());
+    assert!(payment.pos.is_none());
+    assert!(payment.payment_transactions[0]
+        .blockage_resolved_date
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_retrieve_pf_pos_payment() {
+    let payment = get_fixture_client("payment/retrieve_pf_pos_payment.json")
+        .retrieve_payment(PaymentId(1045216))
+        .await
+        .unwrap();
+
+    assert_matches!(payment.payment_type, PaymentType::CardPayment);
+    assert_matches!(payment.pos, Some(pos) => {
+        assert_eq!(pos.alias, "sandbox-pf-pos");
+    });
+    assert!(payment.bank_card_holder_name.is_none());
+    assert!(payment.auth_code.is_none());
+    assert!(payment.host_reference.is_none());
+    assert!(payment.trans_id.is_none());
+    assert!(payment.order_id.is_none());
+}
+
+#[tokio::test]
+async fn test_retrieve_masterpass_payment() {
+    let payment = get_fixture_client("payment/retrieve_masterpass_payment.json")
+        .retrieve_payment(PaymentId(1045401))
+        .await
+        .unwrap();
+
+    assert_matches!(payment.payment_type, PaymentType::CardPayment);
+    assert_matches!(payment.payment_source, PaymentSource::Masterpass);
+}
+
+#[tokio::test]
+async fn test_retrieve_bkm_express_payment() {
+    let payment = get_fixture_client("payment/retrieve_bkm_express_payment.json")
+        .retrieve_payment(PaymentId(1045403))
+        .await
+        .unwrap();
+
+    assert_matches!(payment.payment_type, PaymentType::CardPayment);
+    assert_matches!(payment.payment_source, PaymentSource::Api);
+    assert_matches!(payment.card_association, Some(CardAssociation::Visa));
+}
+
+#[tokio::test]
+async fn test_retrieve_pay_by_link_payment() {
+    let payment = get_fixture_client("payment/retrieve_pay_by_link_payment.json")
+        .retrieve_payment(PaymentId(1045217))
+        .await
+        .unwrap();
+
+    assert_matches!(payment.payment_source, PaymentSource::PayByLink);
+    assert!(payment.buyer_member_id.is_none());
+}
+
+#[tokio::test]
+async fn test_retrieve_checkout_form_payment() {
+    let payment = get_fixture_client("payment/retrieve_checkout_form_payment.json")
+        .retrieve_payment(PaymentId(1045218))
+        .await
+        .unwrap();
+
+    assert_matches!(payment.payment_type, PaymentType::CardPayment);
+    assert_matches!(payment.payment_source, PaymentSource::CheckoutForm);
+    assert!(payment.pos.is_some());
+}
+
+#[tokio::test]
+async fn test_pay_with_reward_money() {
+    let card = card();
+    let params = RetrieveLoyaltiesRequest::builder()
+        .card_number(card.card_number.clone())
+        .expire_year(card.expire_year.clone())
+        .expire_month(card.expire_month.clone())
+        .cvc(card.cvc.clone())
+        .build();
+
+    let loyalties = get_fixture_client("payment/retrieve_loyalties.json")
+        .retrieve_loyalties(params)
+        .await
+        .unwrap();
+    assert_eq!(loyalties.loyalties.len(), 1);
+    let loyalty = loyalties.loyalties[0].clone();
+    assert_matches!(loyalty.loyalty_type, LoyaltyType::RewardMoney);
+
+    let card = Card {
+        loyalty: Some(loyalty),
+        ..card
+    };
+    let payment = get_fixture_client("payment/create_payment.json")
+        .create_payment(
+            create_payment_request("e7a4b2c9-3d5f-4a1e-b8c6-0f9e2d1a7b04", card),
+            &IdempotencyKey::random(),
+        )
+        .await
+        .unwrap();
+
+    assert_matches!(payment.payment_status, PaymentStatus::Success);
+    assert_matches!(payment.loyalty, Some(loyalty) => {
+        assert_eq!(loyalty.reward.unwrap().card_reward_money, Decimal::new(136, 2));
+    });
+}
+
+#[tokio::test]
+async fn test_three_ds_payment() {
+    let client = get_fixture_client("payment/three_ds_payment.json");
+
+    let init = client
+        .init_3ds_payment(
+            InitThreeDSPaymentRequest::builder()
+                .payment(create_payment_request(
+                    "1c8f5e2a-7b9d-4e3c-a6f0-5d4b3a2c1e05",
+                    card(),
+                ))
+                .callback_url("https://www.your-website.com/craftgate-3ds-callback".to_owned())
+                .build(),
+        )
+        .await
+        .unwrap();
+    assert_matches!(init.payment_status, PaymentStatus::InitThreeds);
+    assert!(String::from_utf8(init.html().unwrap())
+        .unwrap()
+        .starts_with("<html>"));
+
+    let payment = client
+        .complete_3ds_payment(init.payment_id, &IdempotencyKey::random())
+        .await
+        .unwrap();
+
+    assert_eq!(payment.id, init.payment_id);
+    assert!(payment.is_three_d_s);
+}
+
+#[tokio::test]
+async fn test_masterpass_payment() {
+    let client = get_fixture_client("payment/masterpass_payment.json");
+
+    let user = client
+        .check_masterpass_user(
+            CheckMasterpassUserRequest::builder()
+                .masterpass_gsm_number("905551111111".to_owned())
+                .build(),
+        )
+        .await
+        .unwrap();
+    assert!(user.is_eligible_to_use_masterpass);
+
+    let loyalties = client
+        .retrieve_masterpass_loyalty(
+            MasterpassRetrieveLoyaltiesRequest::builder()
+                .msisdn("905551111111".to_owned())
+                .card_name("Kartım".to_owned())
+                .bin_number("52586400".to_owned())
+                .build(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(loyalties.loyalties.len(), 1);
+
+    let token = client
+        .generate_masterpass_payment_token(masterpass_token_request(
+            "9b1d3f5a-7c9e-4b1d-a3f5-7c9e1b3d5f75",
+            false,
+        ))
+        .await
+        .unwrap();
+
+    let payment = client
+        .complete_masterpass_payment(
+            MasterpassPaymentCompleteRequest::builder()
+                .reference_id(token.reference_id)
+                .token(token.token)
+                .build(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(payment.id, PaymentId(1045401));
+    assert_matches!(payment.payment_source, PaymentSource::Masterpass);
+}
+
+#[tokio::test]
+async fn test_masterpass_three_ds_payment() {
+    let client = get_fixture_client("payment/masterpass_three_ds_payment.json");
+
+    let token = client
+        .generate_masterpass_payment_token(masterpass_token_request(
+            "2e4a6c8e-0b2d-4f6a-8c0e-2a4c6e8a0b86",
+            true,
+        ))
+        .await
+        .unwrap();
+
+    let init = client
+        .init_3ds_masterpass_payment(
+            MasterpassPaymentThreeDSInitRequest::builder()
+                .reference_id(token.reference_id.clone())
+                .callback_url("https://www.your-website.com/craftgate-3ds-callback".to_owned())
+                .build(),
+        )
+        .await
+        .unwrap();
+    assert!(init.return_url.ends_with(&token.reference_id));
+}
+
+#[tokio::test]
+async fn test_init_garanti_pay_payment() {
+    let init = get_fixture_client("payment/garanti_pay_payment.json")
+        .init_garanti_pay_payment(
+            InitGarantiPayPaymentRequest::builder()
+                .price(Decimal::new(100, 0))
+                .paid_price(Decimal::new(100, 0))
+                .conversation_id(Some("6c8e0a2c-4e6a-4c8e-a0c2-4e6a8c0e2a97".to_owned()))
+                .client_ip(Some("127.0.0.1".to_owned()))
+                .callback_url(
+                    "https://www.your-website.com/craftgate-garanti-pay-callback".to_owned(),
+                )
+                .installments(Some(vec![
+                    GarantiPayInstallment::builder()
+                        .number(1)
+                        .total_price(Decimal::new(100, 0))
+                        .build(),
+                    GarantiPayInstallment::builder()
+                        .number(3)
+                        .total_price(Decimal::new(1035, 1))
+                        .build(),
+                ]))
+                .enable_installments(Some(true))
+                .items(vec![PaymentItem::builder()
+                    .name(Some("Item 1".to_owned()))
+                    .price(Decimal::new(100, 0))
+                    .build()])
+                .build(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(init.payment_id, PaymentId(1045402));
+    let html = String::from_utf8(init.html().unwrap()).unwrap();
+    assert!(html.contains("garanti-pay-form"));
+}
+
+#[tokio::test]
+async fn test_bkm_express_payment() {
+    let client = get_fixture_client("payment/bkm_express_payment.json");
+
+    let init = client
+        .init_bkm_express_payment(
+            InitBkmExpressRequest::builder()
+                .price(Decimal::new(100, 0))
+                .paid_price(Decimal::new(100, 0))
+                .conversation_id(Some("7d9f1b3d-5f7b-4d9f-b1d3-5f7b9d1f3b08".to_owned()))
+                .items(vec![PaymentItem::builder()
+                    .name(Some("Item 1".to_owned()))
+                    .price(Decimal::new(100, 0))
+                    .build()])
+                .build(),
+        )
+        .await
+        .unwrap();
+
+    let payment = client
+        .complete_bkm_express_payment(
+            CompleteBkmExpressRequest::builder()
+                .status(true)
+                .message("Ödeme başarılı".to_owned())
+                .ticket_id(init.id.clone())
+                .build(),
+            &IdempotencyKey::random(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(payment.id, PaymentId(1045403));
+    assert_matches!(payment.payment_status, PaymentStatus::Success);
+
+    let retrieved = client.retrieve_bkm_express_payment(&init.id).await.unwrap();
+    assert_eq!(retrieved.id, payment.id);
+}
+
+#[tokio::test]
+async fn test_apple_pay_payment() {
+    let client = get_fixture_client("payment/apple_pay_payment.json");
+
+    let session = client
+        .retrieve_apple_pay_merchant_session(
+            ApplePayMerchantSessionRequest::builder()
+                .domain("www.your-website.com".to_owned())
+                .display_name("Your Website".to_owned())
+                .validation_url(
+                    "https://apple-pay-gateway-cert.apple.com/paymentservices/startSession"
+                        .to_owned(),
+                )
+                .build(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(session.0["domainName"], "www.your-website.com");
+
+    let card = Card::builder()
+        .wallet_token(Some(
+            WalletToken::builder()
+                .token_type(WalletTokenType::ApplePay)
+                .token(r#"{"version":"EC_v1","data":"..."}"#.to_owned())
+                .build(),
+        ))
+        .build();
+    let payment = client
+        .create_payment(
+            create_payment_request("8e0a2c4e-6a8c-4e0a-8c4e-6a8c0e2a4c19", card),
+            &IdempotencyKey::random(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(payment.id, PaymentId(1045404));
+    assert_matches!(payment.payment_status, PaymentStatus::Success);
+    assert_eq!(payment.last_four_digits.as_deref(), Some("4531"));
+}
+
+#[tokio::test]
+async fn test_multi_payment() {
+    let client = get_fixture_client("payment/multi_payment.json");
+
+    let checkout = client
+        .initiate_checkout_payment(
+            CheckoutPaymentInitiationRequest::builder()
+                .price(Decimal::new(100, 0))
+                .paid_price(Decimal::new(100, 0))
+                .conversation_id(Some("9f1b3d5f-7b9d-4f1b-a3d5-7b9d1f3b5d20".to_owned()))
+                .callback_url("https://www.your-website.com/craftgate-checkout-callback".to_owned())
+                .items(vec![PaymentItem::builder()
+                    .name(Some("Item 1".to_owned()))
+                    .price(Decimal::new(100, 0))
+                    .build()])
+                .allow_multi_payment(Some(true))
+                .build(),
+        )
+        .await
+        .unwrap();
+
+    let multi_payment = client
+        .retrieve_multi_payment(&checkout.token)
+        .await
+        .unwrap();
+
+    assert_matches!(
+        multi_payment.multi_payment_status,
+        MultiPaymentStatus::Created
+    );
+    assert_eq!(multi_payment.remaining_amount, Decimal::new(30, 0));
+    assert_eq!(
+        multi_payment.payment_ids,
+        [PaymentId(1045405), PaymentId(1045406)]
+    );
+}
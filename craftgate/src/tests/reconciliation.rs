@@ -0,0 +1,30 @@
+#[cfg(test)]
+use crate::{
+    id::PaymentId,
+    reconciliation::{reconcile, ReconciliationStatus},
+    tests::get_fixture_client,
+};
+#[cfg(test)]
+use rust_decimal::Decimal;
+#[cfg(test)]
+use time::macros::datetime;
+
+#[tokio::test]
+async fn test_reconcile() {
+    let client = get_fixture_client("reconciliation/reconcile.json");
+
+    let report = reconcile(
+        &client,
+        datetime!(2023-11-02 00:00 +3),
+        datetime!(2023-11-03 00:00 +3),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(report.entries.len(), 1);
+    let entry = &report.entries[0];
+    assert_eq!(entry.payment_id, PaymentId(1045210));
+    assert_eq!(entry.status, ReconciliationStatus::Matched);
+    assert_eq!(entry.actual.merchant_payout_amount, Decimal::new(975, 1));
+    assert_eq!(report.discrepancies().count(), 0);
+}
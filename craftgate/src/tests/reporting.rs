@@ -0,0 +1,37 @@
+#[cfg(test)]
+use crate::{
+    client::CraftgateError,
+    id::PaymentId,
+    request::payment::PaymentType,
+    request::reporting::{transaction_report::parse_transaction_report, ReportFileType},
+    tests::get_fixture_client,
+};
+#[cfg(test)]
+use assert_matches::assert_matches;
+#[cfg(test)]
+use rust_decimal::Decimal;
+#[cfg(test)]
+use time::macros::date;
+
+#[tokio::test]
+async fn test_daily_reports() {
+    let client = get_fixture_client("reporting/daily_reports.json");
+
+    let report = client
+        .retrieve_daily_transaction_report(date!(2023 - 11 - 07), ReportFileType::Csv)
+        .await
+        .unwrap();
+    let rows = parse_transaction_report(&report).unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].payment_id, PaymentId(1045213));
+    assert_eq!(rows[0].merchant_payout_amount, Some(Decimal::new(461, 2)));
+    assert_matches!(rows[1].payment_type, PaymentType::DepositPayment);
+
+    let err = client
+        .retrieve_daily_payment_report(date!(2023 - 11 - 08), ReportFileType::Xlsx)
+        .await
+        .unwrap_err();
+    assert_matches!(err, CraftgateError::ValidationError { description, .. } => {
+        assert_eq!(description, "Report is not ready yet");
+    });
+}
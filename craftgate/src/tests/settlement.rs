@@ -0,0 +1,76 @@
+#[cfg(test)]
+use crate::{
+    id::{MemberId, PayoutAccountId},
+    request::settlement::{
+        instant_wallet_settlement::CreateInstantWalletSettlementRequest,
+        payout_account::{
+            AccountOwner, CreatePayoutAccountRequest, SearchPayoutAccountsRequest,
+            UpdatePayoutAccountRequest,
+        },
+        SettlementResultStatus,
+    },
+    request::Currency,
+    tests::get_fixture_client,
+};
+
+#[tokio::test]
+async fn test_create_instant_wallet_settlement() {
+    let settlement = get_fixture_client("settlement/instant_wallet_settlement.json")
+        .create_instant_wallet_settlement(
+            CreateInstantWalletSettlementRequest::builder()
+                .excluded_sub_merchant_member_ids(vec![MemberId(116212)])
+                .build(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        settlement.settlement_result_status,
+        SettlementResultStatus::Success
+    );
+}
+
+#[tokio::test]
+async fn test_payout_account_lifecycle() {
+    let client = get_fixture_client("settlement/payout_accounts.json");
+
+    let created = client
+        .create_payout_account(
+            CreatePayoutAccountRequest::builder()
+                .external_account_id("wise-account-1".to_owned())
+                .currency(Currency::Usd)
+                .account_owner(AccountOwner::SubMerchantMember)
+                .sub_merchant_member_id(Some(MemberId(116213)))
+                .build(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(created.id, PayoutAccountId(31));
+
+    client
+        .update_payout_account(
+            created.id,
+            UpdatePayoutAccountRequest::builder()
+                .external_account_id("wise-account-2".to_owned())
+                .build(),
+        )
+        .await
+        .unwrap();
+
+    let accounts = client
+        .search_payout_accounts(
+            SearchPayoutAccountsRequest::builder()
+                .sub_merchant_member_id(Some(MemberId(116213)))
+                .build(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(accounts.items.len(), 1);
+    assert_eq!(accounts.items[0].external_account_id, "wise-account-2");
+    assert_eq!(
+        accounts.items[0].account_owner,
+        AccountOwner::SubMerchantMember
+    );
+
+    client.delete_payout_account(created.id).await.unwrap();
+}
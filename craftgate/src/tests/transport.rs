@@ -0,0 +1,171 @@
+#[cfg(test)]
+use crate::{
+    client::CraftgateClient,
+    client::CraftgateError,
+    id::MemberId,
+    request::onboarding::create_member::CreateMemberRequest,
+    signature::{signature, X_API_KEY, X_RND_KEY, X_SIGNATURE},
+    transport::{HttpRequest, HttpResponse, Operation, Transport, TransportError},
+};
+#[cfg(test)]
+use assert_matches::assert_matches;
+#[cfg(test)]
+use async_trait::async_trait;
+#[cfg(test)]
+use bytes::Bytes;
+#[cfg(test)]
+use http::{HeaderMap, Method, StatusCode};
+#[cfg(test)]
+use std::sync::{Arc, Mutex};
+#[cfg(test)]
+use url::Url;
+
+/// Transport answering every request with the same response, without sending anything
+#[cfg(test)]
+#[derive(Clone)]
+struct StaticTransport {
+    response: Option<serde_json::Value>,
+    requests: Arc<Mutex<Vec<HttpRequest>>>,
+}
+
+#[cfg(test)]
+impl StaticTransport {
+    fn new(response: Option<serde_json::Value>) -> Self {
+        Self {
+            response,
+            requests: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    fn client(&self) -> CraftgateClient<StaticTransport> {
+        CraftgateClient::new_with_transport(
+            "api-key",
+            "secret-key",
+            Url::parse("https://api.craftgate.io").unwrap(),
+            self.clone(),
+        )
+    }
+}
+
+#[cfg(test)]
+#[async_trait]
+impl Transport for StaticTransport {
+    type Error = TransportError;
+
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, Self::Error> {
+        self.requests.lock().unwrap().push(request);
+
+        let body = self.response.as_ref().ok_or("connection reset")?;
+
+        Ok(HttpResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: Bytes::from(body.to_string()),
+        })
+    }
+}
+
+#[cfg(test)]
+fn member_json() -> serde_json::Value {
+    serde_json::json!({
+        "data": {
+            "id": 89508,
+            "createdDate": "2023-10-24T11:52:37",
+            "updatedDate": null,
+            "status": "ACTIVE",
+            "isBuyer": true,
+            "isSubMerchant": false,
+            "memberType": null,
+            "memberExternalId": "d8fa867b-000b-4b96-ad3c-43ea22e65e3f",
+            "name": null,
+            "address": "Suadiye Mah. Örnek Cd. No:23, 34740 Kadıköy/İstanbul",
+            "email": "haluk.demir@example.com",
+            "iban": null,
+            "phoneNumber": "905551111111",
+            "legalCompanyTitle": null,
+            "taxOffice": null,
+            "taxNumber": null,
+            "contactName": "Haluk",
+            "contactSurname": "Demir",
+            "identityNumber": null,
+            "subMerchantMaximumAllowedNegativeBalance": 0.0
+        }
+    })
+}
+
+#[cfg(test)]
+fn assert_signed(request: &HttpRequest) {
+    let random_string = request.headers[X_RND_KEY].to_str().unwrap();
+    let expected = signature(
+        request.url.as_str(),
+        "api-key",
+        "secret-key",
+        random_string,
+        request.body.as_deref(),
+    );
+
+    assert_eq!(request.headers[X_API_KEY], "api-key");
+    assert_eq!(request.headers[X_SIGNATURE], expected.as_str());
+}
+
+#[tokio::test]
+async fn test_signs_and_decodes_with_custom_transport() {
+    let transport = StaticTransport::new(Some(member_json()));
+
+    let member = transport
+        .client()
+        .retrieve_member(MemberId(89508))
+        .await
+        .unwrap();
+    assert_matches!(member, Some(member) => {
+        assert_eq!(member.email, "haluk.demir@example.com");
+    });
+
+    let requests = transport.requests.lock().unwrap();
+    assert_eq!(requests[0].method, Method::GET);
+    assert_eq!(
+        requests[0].url.as_str(),
+        "https://api.craftgate.io/onboarding/v1/members/89508"
+    );
+    assert_eq!(requests[0].operation, Operation::Safe);
+    assert_eq!(requests[0].body, None);
+    assert_signed(&requests[0]);
+}
+
+#[tokio::test]
+async fn test_signs_request_body() {
+    let transport = StaticTransport::new(Some(member_json()));
+
+    transport
+        .client()
+        .create_member(
+            CreateMemberRequest::builder()
+                .member_external_id("d8fa867b-000b-4b96-ad3c-43ea22e65e3f".to_owned())
+                .address("Suadiye Mah. Örnek Cd. No:23, 34740 Kadıköy/İstanbul".to_owned())
+                .email("haluk.demir@example.com".to_owned())
+                .phone_number("905551111111".to_owned())
+                .build(),
+        )
+        .await
+        .unwrap();
+
+    let requests = transport.requests.lock().unwrap();
+    assert_eq!(requests[0].method, Method::POST);
+    assert_eq!(requests[0].operation, Operation::Mutation);
+    assert!(requests[0].body.is_some());
+    assert_signed(&requests[0]);
+}
+
+#[tokio::test]
+async fn test_transport_errors_are_ambiguous() {
+    let transport = StaticTransport::new(None);
+
+    let err = transport
+        .client()
+        .retrieve_member(MemberId(89508))
+        .await
+        .unwrap_err();
+
+    assert_matches!(err, CraftgateError::Transport(_));
+    assert!(err.is_ambiguous());
+}
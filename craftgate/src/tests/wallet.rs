@@ -0,0 +1,122 @@
+#[cfg(test)]
+use crate::{
+    id::{MemberId, PaymentId, WalletId},
+    request::payment::{Card, PaymentStatus, PaymentType},
+    request::wallet::{
+        deposit_payment::{
+            CreateDepositPaymentRequest, InitCheckoutDepositPaymentRequest,
+            InitThreeDSDepositPaymentRequest,
+        },
+        fund_transfer_deposit::CreateFundTransferDepositPaymentRequest,
+        WalletTransactionType,
+    },
+    tests::get_fixture_client,
+};
+#[cfg(test)]
+use assert_matches::assert_matches;
+#[cfg(test)]
+use rust_decimal::Decimal;
+
+#[cfg(test)]
+fn deposit_request(conversation_id: &str) -> CreateDepositPaymentRequest {
+    CreateDepositPaymentRequest::builder()
+        .price(Decimal::new(150, 0))
+        .buyer_member_id(MemberId(116210))
+        .conversation_id(Some(conversation_id.to_owned()))
+        .card(
+            Card::builder()
+                .card_holder_name(Some("Haluk Demir".to_owned()))
+                .card_number(Some("5258640000000001".to_owned()))
+                .expire_year(Some("2044".to_owned()))
+                .expire_month(Some("07".to_owned()))
+                .cvc(Some("000".to_owned()))
+                .build(),
+        )
+        .build()
+}
+
+#[tokio::test]
+async fn test_create_deposit_payment() {
+    let deposit = get_fixture_client("wallet/create_deposit_payment.json")
+        .create_deposit_payment(deposit_request("3f1b7a9e-2c4d-4e8a-9b6f-1d0c5e7a2b31"))
+        .await
+        .unwrap();
+
+    assert_matches!(deposit.payment_type, PaymentType::DepositPayment);
+    assert_matches!(deposit.payment_status, PaymentStatus::Success);
+    assert_matches!(deposit.wallet_transaction, Some(transaction) => {
+        assert_matches!(
+            transaction.wallet_transaction_type,
+            WalletTransactionType::DepositFromCard
+        );
+        assert_eq!(transaction.amount, Decimal::new(150, 0));
+    });
+}
+
+#[tokio::test]
+async fn test_three_ds_deposit_payment() {
+    let client = get_fixture_client("wallet/three_ds_deposit_payment.json");
+
+    let init = client
+        .init_3ds_deposit_payment(
+            InitThreeDSDepositPaymentRequest::builder()
+                .deposit(deposit_request("8e2d4c6a-1b3f-4a5e-8c7d-9f0a1b2c3d42"))
+                .callback_url("https://www.your-website.com/craftgate-3ds-callback".to_owned())
+                .build(),
+        )
+        .await
+        .unwrap();
+    assert_matches!(init.payment_status, PaymentStatus::InitThreeds);
+    assert_eq!(init.payment_id, PaymentId(1045302));
+
+    let deposit = client
+        .complete_3ds_deposit_payment(init.payment_id)
+        .await
+        .unwrap();
+
+    assert_eq!(deposit.id, init.payment_id);
+    assert_matches!(deposit.wallet_transaction, Some(transaction) => {
+        assert_eq!(transaction.wallet_id, WalletId(51012));
+        assert_eq!(transaction.amount, Decimal::new(150, 0));
+    });
+}
+
+#[tokio::test]
+async fn test_init_checkout_deposit_payment() {
+    let checkout = get_fixture_client("wallet/checkout_deposit_payment.json")
+        .init_checkout_deposit_payment(
+            InitCheckoutDepositPaymentRequest::builder()
+                .price(Decimal::new(150, 0))
+                .buyer_member_id(MemberId(116210))
+                .conversation_id(Some("5a7c9e1b-3d5f-4b7a-9c1e-2f4a6b8c0d53".to_owned()))
+                .callback_url("https://www.your-website.com/craftgate-checkout-callback".to_owned())
+                .build(),
+        )
+        .await
+        .unwrap();
+
+    assert!(checkout
+        .page_url
+        .starts_with("https://sandbox-checkout.craftgate.io"));
+}
+
+#[tokio::test]
+async fn test_create_fund_transfer_deposit_payment() {
+    let deposit = get_fixture_client("wallet/fund_transfer_deposit_payment.json")
+        .create_fund_transfer_deposit_payment(
+            CreateFundTransferDepositPaymentRequest::builder()
+                .price(Decimal::new(500, 0))
+                .buyer_member_id(MemberId(116211))
+                .conversation_id(Some("c4e6a8b0-2d4f-4a6c-8e0b-3a5c7e9f1b64".to_owned()))
+                .build(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(deposit.buyer_member_id, MemberId(116211));
+    assert_matches!(
+        deposit.wallet_transaction.wallet_transaction_type,
+        WalletTransactionType::DepositFromFundTransfer
+    );
+    assert_eq!(deposit.wallet_transaction.amount, Decimal::new(500, 0));
+}
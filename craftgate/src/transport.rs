@@ -0,0 +1,192 @@
+//! Requests the client sends, independent of the HTTP stack sending them.
+//!
+//! The client builds, signs and decodes requests itself and hands them to a [`Transport`] for
+//! sending. [`ReqwestTransport`] is used by default, other HTTP stacks (hyper, a WASM `fetch`
+//! binding or a test double) can be plugged in with
+//! [`CraftgateClient::new_with_transport`](crate::client::CraftgateClient::new_with_transport).
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::header::HeaderMap;
+#[cfg(any(feature = "onboarding", feature = "payment", feature = "settlement"))]
+use http::header::{HeaderValue, CONTENT_TYPE};
+use http::{Method, StatusCode};
+#[cfg(any(
+    feature = "onboarding",
+    feature = "payment",
+    feature = "settlement",
+    feature = "bank-account-tracking"
+))]
+use serde::Serialize;
+use thiserror::Error;
+#[cfg(any(
+    feature = "onboarding",
+    feature = "payment",
+    feature = "settlement",
+    feature = "bank-account-tracking"
+))]
+use time::UtcOffset;
+use url::Url;
+
+use crate::client::CraftgateError;
+#[cfg(any(
+    feature = "onboarding",
+    feature = "payment",
+    feature = "settlement",
+    feature = "bank-account-tracking"
+))]
+use crate::request::date;
+
+/// Whether a request can be sent more than once without side effects. Attached to every request
+/// the client sends, requests without it are treated as [`Operation::Mutation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// Lookups that do not change anything on Craftgate, safe to retry
+    Safe,
+    /// Requests creating, updating or deleting records or moving money. These are only retried
+    /// when the request could not reach Craftgate at all
+    Mutation,
+}
+
+/// Request to the Craftgate API. Sent by a [`Transport`] after the client signed it, so the url,
+/// headers and body must be sent unchanged.
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: HeaderMap,
+    /// Body of the request, `None` for requests without one
+    pub body: Option<Vec<u8>>,
+    pub operation: Operation,
+}
+
+impl HttpRequest {
+    pub fn new(method: Method, url: Url, operation: Operation) -> Self {
+        Self {
+            method,
+            url,
+            headers: HeaderMap::new(),
+            body: None,
+            operation,
+        }
+    }
+
+    /// Appends `query` to the query string of the url, with timestamps in `offset`
+    #[cfg(any(
+        feature = "onboarding",
+        feature = "payment",
+        feature = "settlement",
+        feature = "bank-account-tracking"
+    ))]
+    pub(crate) fn query<Q: Serialize>(
+        mut self,
+        query: &Q,
+        offset: UtcOffset,
+    ) -> Result<Self, RequestEncodingError> {
+        {
+            let mut pairs = self.url.query_pairs_mut();
+            date::with_offset(offset, || {
+                query.serialize(serde_urlencoded::Serializer::new(&mut pairs))
+            })?;
+        }
+        if let Some("") = self.url.query() {
+            self.url.set_query(None);
+        }
+
+        Ok(self)
+    }
+
+    /// Sets `body` serialized to JSON as the request body, with timestamps in `offset`
+    #[cfg(any(feature = "onboarding", feature = "payment", feature = "settlement"))]
+    pub(crate) fn json<B: Serialize>(
+        mut self,
+        body: &B,
+        offset: UtcOffset,
+    ) -> Result<Self, RequestEncodingError> {
+        self.body = Some(date::with_offset(offset, || serde_json::to_vec(body))?);
+        self.headers
+            .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+        Ok(self)
+    }
+}
+
+/// Response received from the Craftgate API
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+#[derive(Error, Debug)]
+pub enum RequestEncodingError {
+    #[error("Request body can not be serialized: {0}")]
+    Body(#[from] serde_json::Error),
+    #[error("Request query can not be serialized: {0}")]
+    Query(#[from] serde_urlencoded::ser::Error),
+}
+
+/// Error type for transports without one of their own, see [`Transport::Error`]
+pub type TransportError = Box<dyn std::error::Error + Send + Sync>;
+
+/// HTTP stack sending the requests of a [`CraftgateClient`](crate::client::CraftgateClient).
+///
+/// Transports are only responsible for moving bytes. Retries, tracing and similar concerns belong
+/// to the transport as well, e.g. [`ReqwestTransport`] runs the middleware configured in
+/// [`ClientOptions`](crate::client::ClientOptions).
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait Transport {
+    /// Error returned when a request could not be sent or its response not received. Use
+    /// [`TransportError`] unless the transport has an error type with a conversion to
+    /// [`CraftgateError`].
+    type Error: Into<CraftgateError>;
+
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, Self::Error>;
+}
+
+/// [`Transport`] sending requests with `reqwest` through a `reqwest_middleware` stack
+#[cfg(feature = "reqwest")]
+#[derive(Clone)]
+pub struct ReqwestTransport {
+    client: reqwest_middleware::ClientWithMiddleware,
+}
+
+#[cfg(feature = "reqwest")]
+impl ReqwestTransport {
+    pub fn new(client: reqwest_middleware::ClientWithMiddleware) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg(feature = "reqwest")]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Transport for ReqwestTransport {
+    type Error = reqwest_middleware::Error;
+
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, Self::Error> {
+        let mut req = reqwest::Request::new(request.method, request.url);
+        *req.headers_mut() = request.headers;
+        *req.body_mut() = request.body.map(reqwest::Body::from);
+
+        // Middleware such as `RetryMiddleware` reads the operation from the extensions.
+        let mut extensions = task_local_extensions::Extensions::new();
+        extensions.insert(request.operation);
+
+        let resp = self
+            .client
+            .execute_with_extensions(req, &mut extensions)
+            .await?;
+        let status = resp.status();
+        let headers = resp.headers().clone();
+        let body = resp.bytes().await?;
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
@@ -1,9 +1,8 @@
-use axum::extract::Query;
-use axum::http::StatusCode;
 use axum::response::{IntoResponse, Redirect, Response};
 use axum::routing::{get, post};
-use axum::{Extension, Form, Json, Router, ServiceExt};
+use axum::{Extension, Form, Json, Router};
 use craftgate::client::CraftgateClient;
+use craftgate::id::CheckoutToken;
 use craftgate::request::payment::checkout_payment::CheckoutPaymentInitiationRequest;
 use craftgate::request::payment::{Payment, PaymentGroup, PaymentItem, PaymentPhase};
 use serde::{Deserialize, Serialize};
@@ -28,7 +27,7 @@ async fn main() {
 
 #[derive(Deserialize, Debug)]
 pub struct CallbackParams {
-    pub token: String,
+    pub token: CheckoutToken,
 }
 
 #[derive(Serialize)]
@@ -43,11 +42,11 @@ async fn callback_handler(
 ) -> Json<CallbackResponse> {
     dbg!(&params);
 
-    let payment = client.checkout_payment_inquiry(params.token).await.unwrap();
-    let payment2 = client
-        .retrieve_payment(payment.id.to_string())
+    let payment = client
+        .checkout_payment_inquiry(&params.token)
         .await
         .unwrap();
+    let payment2 = client.retrieve_payment(payment.id).await.unwrap();
 
     Json(CallbackResponse {
         payment_token: payment,
@@ -61,14 +60,12 @@ async fn payment_handler(Extension(client): Extension<CraftgateClient>) -> Respo
         .paid_price(10.0.try_into().unwrap())
         .payment_group(PaymentGroup::Product)
         .payment_phase(PaymentPhase::Auth)
-        .external_id("test123".to_owned())
+        .external_id(Some("test123".to_owned()))
         .callback_url("http://127.0.0.1:3000/callback".to_owned())
-        .items(vec![PaymentItemBuilder::default()
+        .items(vec![PaymentItem::builder()
             .price(10.0.try_into().unwrap())
-            .build()
-            .unwrap()])
-        .build()
-        .unwrap();
+            .build()])
+        .build();
 
     let resp = client
         .initiate_checkout_payment(payment_initiation_request)